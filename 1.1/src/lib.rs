@@ -0,0 +1,106 @@
+use aoc_common::{certificate_field, checked_product, json, parse_numbers, read_parsed, Certificate, Certified, Error, InputSource, Solution, Unsolved};
+
+#[cfg(not(feature = "bigint"))]
+pub type Product = i32;
+#[cfg(feature = "bigint")]
+pub type Product = aoc_common::BigInt;
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<i32>, Error> {
+    read_parsed(source, parse_numbers)
+}
+
+pub fn find_pair_summing_to(numbers: &Vec<i32>, target: i32) -> Option<(i32, i32)> {
+    for (idx, i) in numbers.iter().enumerate() {
+        for j in numbers[(idx + 1)..].iter() {
+            if i.checked_add(*j) == Some(target) {
+                return Some((*i, *j));
+            }
+        }
+    }
+
+    return None;
+}
+
+pub fn entry_product(entries: &[i32]) -> Result<Product, Error> {
+    return checked_product(entries.iter().map(|n| Product::from(*n)), "the product of the entries");
+}
+
+// The entries must be `n_entries` different lines of the input, summing to 2020 with `answer` as
+// their product. Part 2 in the 1.2 crate checks its triples with this too.
+pub fn check_entries(numbers: &Vec<i32>, entries: &Vec<i32>, n_entries: usize, answer: &str) -> Result<(), Error> {
+    if entries.len() != n_entries {
+        return Err(Error::Certificate(format!("{} entries instead of {}", entries.len(), n_entries)));
+    }
+    let mut unused = numbers.clone();
+    for entry in entries {
+        match unused.iter().position(|n| n == entry) {
+            Some(idx) => { unused.swap_remove(idx); },
+            None => return Err(Error::Certificate(format!("{} is not an unused entry", entry))),
+        }
+    }
+    let sum: i64 = entries.iter().map(|n| *n as i64).sum();
+    if sum != 2020 {
+        return Err(Error::Certificate(format!("the entries sum to {}", sum)));
+    }
+    let product = entry_product(entries)?;
+    if product.to_string() != answer {
+        return Err(Error::Certificate(format!("the entries multiply to {}", product)));
+    }
+    return Ok(());
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = Product;
+    type Answer2 = Unsolved;
+
+    const VERSION: u32 = 3;
+
+    fn parse(text: &str) -> Result<Vec<i32>, Error> {
+        Ok(parse_numbers(text)?)
+    }
+
+    fn part1(numbers: &Vec<i32>) -> Result<Certified<Product>, Error> {
+        match find_pair_summing_to(numbers, 2020) {
+            Some((i, j)) => Ok(Certified::new(entry_product(&[i, j])?, json!({ "entries": [i, j] }))),
+            None => Err(Error::NoAnswer("no pair sums to 2020".to_string())),
+        }
+    }
+
+    // Part 2 lives in the 1.2 crate.
+    fn part2(_numbers: &Vec<i32>) -> Result<Certified<Unsolved>, Error> {
+        Err(Error::Unsolved)
+    }
+
+    fn check(numbers: &Vec<i32>, _part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_entries(numbers, &certificate_field(certificate, "entries")?, 2, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_entries() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456, 1010];
+        let certificate = Day1::part1(&numbers).unwrap().certificate;
+        assert_eq!(certificate, json!({ "entries": [1721, 299] }));
+        assert!(Day1::check(&numbers, 1, &certificate, "514579").is_ok());
+
+        let err = Day1::check(&numbers, 1, &certificate, "514578").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the entries multiply to 514579");
+        let err = Day1::check(&numbers, 1, &json!({ "entries": [1010, 1010] }), "1020100").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 1010 is not an unused entry");
+        let err = Day1::check(&numbers, 1, &json!({ "entries": [979, 366] }), "358314").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the entries sum to 1345");
+    }
+
+    #[test]
+    fn test_entry_does_not_pair_with_itself() {
+        assert_eq!(find_pair_summing_to(&vec![1010, 1721], 2020), None);
+        assert_eq!(find_pair_summing_to(&vec![1010, 1721, 1010], 2020), Some((1010, 1010)));
+    }
+}
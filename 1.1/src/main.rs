@@ -1,38 +1,31 @@
 use std::process;
-use std::fs;
+
+use aoc1_1::{entry_product, find_pair_summing_to, slurp_input};
+use aoc_common::{init_logging, InputSource};
 
 fn main() {
-    let filename = "input";
-    let contents = match fs::read_to_string(filename) {
-        Ok(istr) => istr,
+    init_logging(None);
+    let source = InputSource::from_args("input");
+
+    let numbers = match slurp_input(source) {
+        Ok(numbers) => numbers,
         Err(err) => {
-            eprintln!("error: {:?}", err);
+            eprintln!("error: {}", err);
             process::exit(1);
         }
     };
 
-    let numbers: Vec<i32> = contents.split_whitespace().map(
-        |istr|
-
-        match istr.parse::<i32>() {
-            Ok(n) => n,
+    match find_pair_summing_to(&numbers, 2020) {
+        Some((i, j)) => match entry_product(&[i, j]) {
+            Ok(k) => println!("{} * {} = {}", i, j, k),
             Err(err) => {
-                eprintln!("error: {:?}", err);
+                eprintln!("error: {}", err);
                 process::exit(1);
             }
-        }
-    ).collect();
-
-    for i in numbers.iter() {
-        for j in numbers.iter() {
-            if (i + j == 2020) {
-                let k = i * j;
-                println!("{} * {} = {}", i, j, k);
-                process::exit(0);
-            }
+        },
+        None => {
+            eprintln!("error: no answer found.");
+            process::exit(1);
         }
     }
-
-    eprintln!("error: no answer found.");
-    process::exit(1);
 }
@@ -0,0 +1,66 @@
+use itertools::Itertools;
+
+use aoc_common::{certificate_field, json, parse_numbers, read_parsed, Certificate, Certified, Error, InputSource, Solution, Unsolved};
+pub use aoc1_1::{check_entries, entry_product, Product};
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<i32>, Error> {
+    read_parsed(source, parse_numbers)
+}
+
+pub fn find_triple_summing_to(numbers: &Vec<i32>, target: i32) -> Option<Vec<i32>> {
+    for candidates in numbers.clone().into_iter().combinations(3) {
+        // A sum too large for i32 cannot be the target.
+        let sum: Option<i32> = candidates.iter().try_fold(0_i32, |sum, n| sum.checked_add(*n));
+        if sum == Some(target) {
+            return Some(candidates);
+        }
+    }
+
+    return None;
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i32>;
+    type Answer1 = Unsolved;
+    type Answer2 = Product;
+
+    const VERSION: u32 = 2;
+
+    fn parse(text: &str) -> Result<Vec<i32>, Error> {
+        Ok(parse_numbers(text)?)
+    }
+
+    // Part 1 lives in the 1.1 crate.
+    fn part1(_numbers: &Vec<i32>) -> Result<Certified<Unsolved>, Error> {
+        Err(Error::Unsolved)
+    }
+
+    fn part2(numbers: &Vec<i32>) -> Result<Certified<Product>, Error> {
+        match find_triple_summing_to(numbers, 2020) {
+            Some(candidates) => Ok(Certified::new(entry_product(&candidates)?, json!({ "entries": candidates }))),
+            None => Err(Error::NoAnswer("no three entries sum to 2020".to_string())),
+        }
+    }
+
+    fn check(numbers: &Vec<i32>, _part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_entries(numbers, &certificate_field(certificate, "entries")?, 3, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_entries() {
+        let numbers = vec![1721, 979, 366, 299, 675, 1456];
+        let certificate = Day1::part2(&numbers).unwrap().certificate;
+        assert_eq!(certificate, json!({ "entries": [979, 366, 675] }));
+        assert!(Day1::check(&numbers, 2, &certificate, "241861950").is_ok());
+
+        let err = Day1::check(&numbers, 2, &json!({ "entries": [1721, 299] }), "514579").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 2 entries instead of 3");
+    }
+}
@@ -1,40 +1,35 @@
 use std::process;
-use std::fs;
 
-use itertools::Itertools;
+use aoc1_2::{entry_product, find_triple_summing_to, slurp_input};
+use aoc_common::{init_logging, InputSource};
 
 fn main() {
-    let filename = "input";
-    let contents = match fs::read_to_string(filename) {
-        Ok(istr) => istr,
+    init_logging(None);
+    let source = InputSource::from_args("input");
+
+    let numbers = match slurp_input(source) {
+        Ok(numbers) => numbers,
         Err(err) => {
-            eprintln!("error: {:?}", err);
+            eprintln!("error: {}", err);
             process::exit(1);
         }
     };
 
-    let numbers: Vec<i32> = contents.split_whitespace().map(
-        |istr|
-
-        match istr.parse::<i32>() {
-            Ok(n) => n.clone(),
-            Err(err) => {
-                eprintln!("error: {:?}", err);
-                process::exit(1);
-            }
-        }
-    ).collect();
-
-    for candidates in numbers.into_iter().combinations(3) {
-        let sum: i32 = candidates.clone().into_iter().sum();
-        if sum == 2020 {
-            let product: i32 = candidates.clone().into_iter().product();
+    match find_triple_summing_to(&numbers, 2020) {
+        Some(candidates) => {
+            let product = match entry_product(&candidates) {
+                Ok(product) => product,
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            };
             let str_candidates: Vec<String> = candidates.iter().map(|n| n.to_string()).collect();
             println!("{} = {}", str_candidates.join(" * "), product);
-            process::exit(0);
+        },
+        None => {
+            eprintln!("error: no answer found.");
+            process::exit(1);
         }
     }
-
-    eprintln!("error: no answer found.");
-    process::exit(1);
 }
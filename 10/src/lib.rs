@@ -0,0 +1,238 @@
+use aoc_common::{certificate_field, check_answer, checked_add, checked_mul, json, parse_numbers, read_parsed, Certificate, Certified, Error, InputSource, Solution};
+
+#[cfg(not(feature = "bigint"))]
+pub type Count = u64;
+#[cfg(feature = "bigint")]
+pub type Count = aoc_common::BigUint;
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<u32>, Error> {
+    return read_parsed(source, parse_numbers);
+}
+
+pub fn find_seq(joltages: &Vec<u32>) -> Result<(bool, Vec<u32>), Error> {
+    let mut seq = joltages.to_vec();
+    seq.sort();
+    let mut prev_j: u32 = 0;
+    for j in &seq {
+        if j - prev_j > 3 {
+            return Ok((false, seq));
+        }
+        prev_j = *j;
+    }
+    seq.push(checked_add(&prev_j, &3, "the device's joltage")?);
+    return Ok((true, seq));
+}
+
+pub fn delta_histo(joltages: &Vec<u32>) -> Vec<u32> {
+    let mut histo: Vec<u32> = vec![0, 0, 0];
+    let mut prev_j: u32 = 0;
+    for j in joltages {
+        let delta = (j - prev_j) as usize;
+        if (1..=3).contains(&delta) {
+            histo[delta - 1] = histo[delta - 1] + 1;
+        }
+        prev_j = *j;
+    }
+    return histo;
+}
+
+pub fn count_solutions(joltages: &Vec<u32>) -> Result<Count, Error> {
+    let (total, _) = count_paths(joltages)?;
+    return Ok(total);
+}
+
+/// The number of arrangements, along with how many ways each adapter in the
+/// chain has of reaching the device.
+pub fn count_paths(joltages: &Vec<u32>) -> Result<(Count, Vec<Count>), Error> {
+    let what = "the number of adapter arrangements";
+    let mut paths: Vec<Count> = vec![Count::from(1_u32); joltages.len()];
+
+    for i in (0..joltages.len() - 1).rev() {
+        let mut from_here = Count::from(0_u32);
+        for j in 1..4 {
+            if i + j < joltages.len()
+                && joltages[i + j] - joltages[i] <= 3 {
+                    from_here = checked_add(&from_here, &paths[i + j], what)?;
+                }
+        }
+        paths[i] = from_here;
+    }
+
+    let mut final_sum = Count::from(0_u32);
+    for j in 0..joltages.len().min(3) {
+        if joltages[j] <= 3 {
+            final_sum = checked_add(&final_sum, &paths[j], what)?;
+        }
+    }
+    return Ok((final_sum, paths));
+}
+
+/// Confirms that `chain` holds every adapter once, in order, each within 3 jolts
+/// of the one before, ending with the device 3 above the highest.
+pub fn check_chain(joltages: &Vec<u32>, chain: &Vec<u32>) -> Result<(), Error> {
+    let mut adapters = joltages.clone();
+    adapters.sort();
+    let highest = adapters.last().cloned().unwrap_or(0);
+    adapters.push(checked_add(&highest, &3, "the device's joltage")?);
+    let mut given = chain.clone();
+    given.sort();
+    if given != adapters {
+        return Err(Error::Certificate("the chain does not use each adapter once, then the device".to_string()));
+    }
+    let mut prev_j = 0;
+    for j in chain {
+        if *j <= prev_j || *j - prev_j > 3 {
+            return Err(Error::Certificate(format!("{} jolts cannot follow {}", j, prev_j)));
+        }
+        prev_j = *j;
+    }
+    Ok(())
+}
+
+/// Confirms the jolt differences along the chain multiply to the answer.
+pub fn check_differences(joltages: &Vec<u32>, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let chain: Vec<u32> = certificate_field(certificate, "chain")?;
+    check_chain(joltages, &chain)?;
+    let ones = chain.iter().zip(std::iter::once(&0).chain(chain.iter())).filter(|(j, prev)| **j - **prev == 1).count();
+    let threes = chain.iter().zip(std::iter::once(&0).chain(chain.iter())).filter(|(j, prev)| **j - **prev == 3).count();
+    check_answer(ones * threes, answer)
+}
+
+/// Confirms that each adapter's count of ways to the device is the sum of
+/// those of the adapters it can plug into, and that those within reach of
+/// the outlet add up to the answer.
+pub fn check_arrangements(joltages: &Vec<u32>, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let chain: Vec<u32> = certificate_field(certificate, "chain")?;
+    check_chain(joltages, &chain)?;
+    // Counts are strings, as they can be too big for JSON numbers.
+    let ways: Vec<String> = certificate_field(certificate, "ways")?;
+    if ways.len() != chain.len() {
+        return Err(Error::Certificate(format!("{} counts of ways for {} adapters", ways.len(), chain.len())));
+    }
+    let ways: Vec<Count> = ways.iter()
+        .map(|w| w.parse().map_err(|_| Error::Certificate(format!("`{}` is not a count", w))))
+        .collect::<Result<_, _>>()?;
+    let what = "the number of adapter arrangements";
+    let reachable_sum = |from: u32, after: usize| -> Result<Count, Error> {
+        let mut sum = Count::from(0_u32);
+        for (j, w) in chain.iter().zip(&ways).skip(after).take_while(|(j, _)| j.saturating_sub(from) <= 3) {
+            if *j > from {
+                sum = checked_add(&sum, w, what)?;
+            }
+        }
+        Ok(sum)
+    };
+    for i in (0..chain.len()).rev() {
+        let expected = if i + 1 == chain.len() { Count::from(1_u32) } else { reachable_sum(chain[i], i + 1)? };
+        if ways[i] != expected {
+            return Err(Error::Certificate(format!("the adapter at {} jolts has {} ways to the device, not {}", chain[i], expected, ways[i])));
+        }
+    }
+    check_answer(reachable_sum(0, 0)?, answer)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = Count;
+
+    fn parse(text: &str) -> Result<Vec<u32>, Error> {
+        Ok(parse_numbers(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(joltages: &Vec<u32>) -> Result<Certified<u32>, Error> {
+        let seq = chained_seq(joltages)?;
+        let histo = delta_histo(&seq);
+        let product = checked_mul(&histo[0], &histo[2], "the product of the 1 and 3 jolt differences")?;
+        Ok(Certified::new(product, json!({ "chain": seq })))
+    }
+
+    fn part2(joltages: &Vec<u32>) -> Result<Certified<Count>, Error> {
+        let seq = chained_seq(joltages)?;
+        let (total, paths) = count_paths(&seq)?;
+        let ways: Vec<String> = paths.iter().map(|w| w.to_string()).collect();
+        Ok(Certified::new(total, json!({ "chain": seq, "ways": ways })))
+    }
+
+    fn check(joltages: &Vec<u32>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_differences(joltages, certificate, answer),
+            _ => check_arrangements(joltages, certificate, answer),
+        }
+    }
+}
+
+fn chained_seq(joltages: &Vec<u32>) -> Result<Vec<u32>, Error> {
+    match find_seq(joltages)? {
+        (true, seq) => Ok(seq),
+        (false, _) => Err(Error::NoAnswer("adapters cannot be chained".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example1() {
+        let joltages = slurp_input("example1").unwrap();
+        let expected_seq = vec![1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22];
+        let (valid, seq) = find_seq(&joltages).unwrap();
+        assert_eq!(valid, true);
+        assert_eq!(seq, expected_seq);
+        let histo: Vec<u32> = delta_histo(&seq);
+        assert_eq!(histo[0], 7);
+        assert_eq!(histo[1], 0);
+        assert_eq!(histo[2], 5);
+
+        let cnt = count_solutions(&seq).unwrap();
+        assert_eq!(cnt, Count::from(8_u32));
+    }
+
+    #[test]
+    fn test_example2() {
+        let joltages = slurp_input("example2").unwrap();
+        let (valid, seq) = find_seq(&joltages).unwrap();
+        assert_eq!(valid, true);
+        let histo: Vec<u32> = delta_histo(&seq);
+        assert_eq!(histo[0], 22);
+        assert_eq!(histo[1], 0);
+        assert_eq!(histo[2], 10);
+
+        let cnt = count_solutions(&seq).unwrap();
+        assert_eq!(cnt, Count::from(19208_u32));
+    }
+
+    #[test]
+    fn test_check_arrangements() {
+        let joltages = slurp_input("example1").unwrap();
+        let solved = Day10::part2(&joltages).unwrap();
+        assert_eq!(solved.certificate["ways"][0], json!("8"));
+        assert!(Day10::check(&joltages, 2, &solved.certificate, "8").is_ok());
+        assert!(Day10::check(&joltages, 1, &solved.certificate, "35").is_ok());
+
+        let mut padded = solved.certificate.clone();
+        padded["ways"][3] = json!("5");
+        let err = Day10::check(&joltages, 2, &padded, "8").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the adapter at 6 jolts has 2 ways to the device, not 5");
+        let err = Day10::check(&joltages, 1, &json!({ "chain": [1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22, 22] }), "35").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the chain does not use each adapter once, then the device");
+        let err = Day10::check(&joltages, 1, &json!({ "chain": [4, 1, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22] }), "35").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 4 jolts cannot follow 0");
+    }
+
+    #[test]
+    fn test_long_chain_overflow() {
+        let joltages: Vec<u32> = (1..=100).collect();
+        let (_, seq) = find_seq(&joltages).unwrap();
+        let cnt = count_solutions(&seq);
+        #[cfg(not(feature = "bigint"))]
+        assert!(matches!(cnt, Err(Error::Overflow(_))));
+        #[cfg(feature = "bigint")]
+        assert!(cnt.unwrap() > Count::from(u64::MAX));
+    }
+}
@@ -0,0 +1,31 @@
+use aoc10::{count_solutions, find_seq, Count};
+use proptest::prelude::*;
+
+// Tries every subset of the adapters, keeping those that chain from the outlet to the device.
+fn brute_force_count(seq: &Vec<u32>) -> u64 {
+    let (adapters, device) = seq.split_at(seq.len() - 1);
+    let mut count = 0;
+    for subset in 0_u32..(1 << adapters.len()) {
+        let mut prev = 0;
+        let mut chains = true;
+        let chosen = adapters.iter().enumerate().filter(|(idx, _)| (subset >> idx) & 1 == 1);
+        for (_, &j) in chosen.chain(device.iter().enumerate()) {
+            chains = chains && j - prev <= 3;
+            prev = j;
+        }
+        if chains {
+            count = count + 1;
+        }
+    }
+    return count;
+}
+
+proptest! {
+    #[test]
+    fn count_solutions_matches_brute_force(gaps in prop::collection::vec(1_u32..=3, 1..=12)) {
+        let joltages: Vec<u32> = gaps.iter().scan(0, |j, gap| { *j = *j + gap; Some(*j) }).collect();
+        let (chains, seq) = find_seq(&joltages).unwrap();
+        prop_assert!(chains);
+        prop_assert_eq!(count_solutions(&seq).unwrap(), Count::from(brute_force_count(&seq)));
+    }
+}
@@ -0,0 +1,17 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc11::{slurp_input, update_until_stable};
+
+fn bench_update_until_stable(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_until_stable");
+    group.sample_size(20);
+    for filename in ["example", "input"].iter() {
+        let floor = slurp_input(*filename).unwrap();
+        group.bench_function(format!("{}/adjacent", filename), |b| b.iter(|| update_until_stable(&floor, 4, 1).unwrap()));
+        group.bench_function(format!("{}/line_of_sight", filename), |b| b.iter(|| update_until_stable(&floor, 5, 0).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_update_until_stable);
+criterion_main!(benches);
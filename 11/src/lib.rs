@@ -0,0 +1,222 @@
+pub mod simulation;
+
+use log::trace;
+
+use aoc_common::{certificate_field, check_answer, count_round, json, read_parsed, Certificate, Certified, Iterations, Error, Grid, InputSource, ParseError, Solution, Tile, DIRECTIONS};
+
+// part 1: 2386
+// part 2: 2091
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Tile for Seat {
+    const EXPECTED: &'static str = "`.`, `L` or `#`";
+
+    fn from_char(ch: char) -> Option<Seat> {
+        match ch {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Seat::Floor => '.',
+            Seat::Empty => 'L',
+            Seat::Occupied => '#',
+        }
+    }
+}
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Grid<Seat>, Error> {
+    return read_parsed(source, parse_floor_table);
+}
+
+// Rows of `.`, `L` and `#`, all as wide as the first.
+pub fn parse_floor_table(contents: &str) -> Result<Grid<Seat>, ParseError> {
+    return Grid::parse(contents);
+}
+
+// A horizon of 0 looks as far as the edge of the floor.
+pub fn update_seats(table: &Grid<Seat>, adj_affinity: u32, horizon: u32) -> (u32, Grid<Seat>) {
+    let reach = if horizon == 0 { usize::MAX } else { horizon as usize };
+    let mut updates: u32 = 0;
+    let mut cloned = table.clone();
+    for (x, y, cell) in table.iter() {
+        if *cell == Seat::Floor {
+            continue;
+        }
+
+        let mut adj_occupied = 0;
+        for (off_x, off_y) in DIRECTIONS.iter() {
+            let seen = table.ray(x, y, *off_x, *off_y).take(reach).find(|adj| **adj != Seat::Floor);
+            if seen == Some(&Seat::Occupied) {
+                adj_occupied = adj_occupied + 1;
+            }
+        }
+        trace!("({}, {}) => {} adj", x, y, adj_occupied);
+        if *cell == Seat::Occupied && adj_occupied >= adj_affinity {
+            cloned[(x, y)] = Seat::Empty;
+            updates = updates + 1;
+        } else if *cell == Seat::Empty && adj_occupied == 0 {
+            cloned[(x, y)] = Seat::Occupied;
+            updates = updates + 1;
+        }
+    }
+    return (updates, cloned);
+}
+
+// Rules that never settle would loop forever, so rounds stop once the solve is cancelled.
+pub fn update_until_stable(input_floor: &Grid<Seat>, adj_affinity: u32, horizon: u32) -> Result<Grid<Seat>, Error> {
+    let mut floor: Grid<Seat> = input_floor.clone();
+    let mut rounds = Iterations::new().reporting("generations", None);
+    loop {
+        rounds.tick()?;
+        let (updates, updated_floor) = update_seats(&floor, adj_affinity, horizon);
+        count_round("seat_updates", updates as u64);
+        if updates == 0 {
+            return Ok(updated_floor);
+        }
+        floor = updated_floor;
+    }
+}
+
+pub fn count_occupied_seats(table: &Grid<Seat>) -> u32 {
+    return table.iter().filter(|(_, _, cell)| **cell == Seat::Occupied).count() as u32;
+}
+
+// Walks the floor by hand rather than through `Grid::ray`, so the checker
+// does not share the solver's idea of what a seat can see.
+fn occupied_in_sight(floor: &Grid<Seat>, x: usize, y: usize, horizon: u32) -> u32 {
+    let mut occupied = 0;
+    for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+        let (mut cx, mut cy) = (x as isize, y as isize);
+        let mut steps = 0;
+        loop {
+            cx = cx + dx;
+            cy = cy + dy;
+            steps = steps + 1;
+            match floor.get(cx, cy) {
+                Some(Seat::Occupied) => { occupied = occupied + 1; break; }
+                Some(Seat::Empty) | None => break,
+                Some(Seat::Floor) if horizon != 0 && steps >= horizon => break,
+                Some(Seat::Floor) => continue,
+            }
+        }
+    }
+    return occupied;
+}
+
+/// Confirms that the certificate's `stable` floor keeps the input's layout, that
+/// no seat in it would change under the rules, and that it has `answer` people seated.
+/// That it is the arrangement the input settles into, and not some other one the
+/// rules leave alone, is taken on trust.
+pub fn check_stable(floor: &Grid<Seat>, adj_affinity: u32, horizon: u32, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let rows: Vec<String> = certificate_field(certificate, "stable")?;
+    let stable: Grid<Seat> = Grid::parse(&rows.join("\n"))
+        .map_err(|err| Error::Certificate(format!("the stable floor does not parse: {}", err)))?;
+    if stable.width() != floor.width() || stable.height() != floor.height() {
+        return Err(Error::Certificate(format!("the stable floor is {}x{}, not {}x{}", stable.width(), stable.height(), floor.width(), floor.height())));
+    }
+    for (x, y, seat) in stable.iter() {
+        if (*seat == Seat::Floor) != (floor[(x, y)] == Seat::Floor) {
+            return Err(Error::Certificate(format!("({}, {}) is floor in one layout and a seat in the other", x, y)));
+        }
+        let occupied = occupied_in_sight(&stable, x, y, horizon);
+        let changes = match seat {
+            Seat::Floor => false,
+            Seat::Empty => occupied == 0,
+            Seat::Occupied => occupied >= adj_affinity,
+        };
+        if changes {
+            return Err(Error::Certificate(format!("the seat at ({}, {}) would still change", x, y)));
+        }
+    }
+    check_answer(count_occupied_seats(&stable), answer)
+}
+
+fn certify_stable(stable: Grid<Seat>) -> Certified<u32> {
+    let rows: Vec<String> = stable.to_string().lines().map(|row| row.to_string()).collect();
+    return Certified::new(count_occupied_seats(&stable), json!({ "stable": rows }));
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Grid<Seat>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Grid<Seat>, Error> {
+        Ok(parse_floor_table(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(floor: &Grid<Seat>) -> Result<Certified<u32>, Error> {
+        Ok(certify_stable(update_until_stable(floor, 4, 1)?))
+    }
+
+    fn part2(floor: &Grid<Seat>) -> Result<Certified<u32>, Error> {
+        Ok(certify_stable(update_until_stable(floor, 5, 0)?))
+    }
+
+    fn check(floor: &Grid<Seat>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_stable(floor, 4, 1, certificate, answer),
+            _ => check_stable(floor, 5, 0, certificate, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::CancelToken;
+
+    #[test]
+    fn test_example1() {
+        let floor: Grid<Seat> = slurp_input("example").unwrap();
+        let stable = update_until_stable(&floor, 4, 1).unwrap();
+        assert_eq!(count_occupied_seats(&stable), 37);
+    }
+
+    #[test]
+    fn test_example2() {
+        let floor: Grid<Seat> = slurp_input("example").unwrap();
+        let stable = update_until_stable(&floor, 5, 0).unwrap();
+        assert_eq!(count_occupied_seats(&stable), 26);
+    }
+
+    #[test]
+    fn test_check_stable() {
+        let floor: Grid<Seat> = slurp_input("example").unwrap();
+        let solved = Day11::part2(&floor).unwrap();
+        assert!(Day11::check(&floor, 2, &solved.certificate, "26").is_ok());
+
+        let err = Day11::check(&floor, 2, &solved.certificate, "25").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: it proves 26, not 25");
+        let err = Day11::check(&floor, 2, &json!({ "stable": ["L.LL"] }), "0").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the stable floor is 4x1, not 10x10");
+        let start: Vec<String> = floor.to_string().lines().map(|row| row.to_string()).collect();
+        let err = Day11::check(&floor, 2, &json!({ "stable": start }), "0").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the seat at (0, 0) would still change");
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let floor: Grid<Seat> = slurp_input("example").unwrap();
+        let token = CancelToken::new();
+        token.cancel();
+        let err = token.run(|| update_until_stable(&floor, 5, 0)).unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 1 iterations");
+    }
+}
@@ -0,0 +1,69 @@
+use aoc_common::{Error, Grid, Simulation};
+
+use crate::{count_occupied_seats, parse_floor_table, update_seats, Seat};
+
+/// Seating rounds, played until nobody moves.
+pub struct SeatSimulation {
+    floor: Grid<Seat>,
+    adj_affinity: u32,
+    horizon: u32,
+    round: u32,
+    updates: u32,
+}
+
+impl SeatSimulation {
+    pub fn new(floor: Grid<Seat>, adj_affinity: u32, horizon: u32) -> SeatSimulation {
+        SeatSimulation{ floor: floor, adj_affinity: adj_affinity, horizon: horizon, round: 0, updates: 0 }
+    }
+}
+
+impl Simulation for SeatSimulation {
+    fn step(&mut self) -> bool {
+        let (updates, floor) = update_seats(&self.floor, self.adj_affinity, self.horizon);
+        if updates == 0 {
+            return false;
+        }
+        self.floor = floor;
+        self.round = self.round + 1;
+        self.updates = updates;
+        return true;
+    }
+
+    fn render(&self) -> String {
+        return self.floor.to_string();
+    }
+
+    fn status(&self) -> String {
+        return format!("round {}: {} seats changed, {} occupied", self.round, self.updates, count_occupied_seats(&self.floor));
+    }
+}
+
+/// The seating rounds of the given part, starting from the floor in `text`.
+pub fn simulate(text: &str, part: u8) -> Result<Box<dyn Simulation>, Error> {
+    let floor = parse_floor_table(text)?;
+    let sim = match part {
+        1 => SeatSimulation::new(floor, 4, 1),
+        2 => SeatSimulation::new(floor, 5, 0),
+        _ => return Err(Error::Unsolved),
+    };
+    return Ok(Box::new(sim));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds_until_stable() {
+        let text = std::fs::read_to_string("example").unwrap();
+        let mut sim = simulate(&text, 1).unwrap();
+        assert_eq!(sim.render().trim_end(), text.trim_end());
+
+        let mut rounds = 0;
+        while sim.step() {
+            rounds = rounds + 1;
+        }
+        assert_eq!(rounds, 5);
+        assert!(sim.status().ends_with(", 37 occupied"));
+    }
+}
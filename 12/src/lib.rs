@@ -0,0 +1,336 @@
+pub mod simulation;
+
+use cgmath::Vector2;
+use cgmath::Point2;
+use cgmath::Deg;
+use cgmath::{Rotation, Rotation2, Basis2};
+use log::trace;
+
+use aoc_common::{certificate_field, check_answer, checked_add, checked_mul, json, parse_integer, read_lines, to_lines, Certificate, Certified, Details, Error, InputSource, ParseError, Solution};
+
+// Part 1: Facing S at (658, 824), with distance of 1482
+// Part 2: Facing (-38, -25) at (-1329, -47410), with distance of 48739
+
+pub const NORTH: char = 'N';
+pub const SOUTH: char = 'S';
+pub const EAST: char = 'E';
+pub const WEST: char = 'W';
+
+pub const FORWARD: char = 'F';
+pub const LEFT: char = 'L';
+pub const RIGHT: char = 'R';
+
+
+pub enum Step {
+    Move { vec: Vector2<i32>, },
+    Rotate { deg: Deg<i32>, },
+    Thrust { coeff: i32, },
+}
+
+impl Step {
+    pub fn new_movement(vec: Vector2<i32>) -> Step {
+        Step::Move{
+            vec: vec,
+        }
+    }
+
+    pub fn new_rotation(deg: i32) -> Step {
+        Step::Rotate{
+            deg: Deg(deg),
+        }
+    }
+
+    pub fn new_forward(distance: i32) -> Step {
+        Step::Thrust{
+            coeff: distance,
+        }
+    }
+}
+
+pub fn new_compass_vec(dir: char, distance: i32) -> Result<Vector2<i32>, Error> {
+    let what = "the distance to move";
+    Ok(match dir {
+        NORTH => Vector2{x: 0, y: distance},
+        SOUTH => Vector2{x: 0, y: checked_mul(&distance, &-1, what)?},
+        WEST => Vector2{x: checked_mul(&distance, &-1, what)?, y: 0},
+        EAST => Vector2{x: distance, y: 0},
+        _ => panic!(),
+    })
+}
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    return read_lines(source);
+}
+
+pub fn decode_step(ln: &String) -> Result<Step, ParseError> {
+    let ch = match ln.chars().next() {
+        Some(ch) if "NSEWLRF".contains(ch) => ch,
+        _ => return Err(ParseError::at_start(ln, "an action `N`, `S`, `E`, `W`, `L`, `R` or `F`")),
+    };
+    let num_str = &ln[ch.len_utf8()..];
+    let num: i32 = parse_integer(ln, num_str)?;
+    if (ch == LEFT || ch == RIGHT) && num % 90 != 0 {
+        return Err(ParseError::at_token(ln, num_str, "a multiple of 90 degrees"));
+    }
+    return Ok(match ch {
+        NORTH | SOUTH | WEST | EAST => match new_compass_vec(ch, num) {
+            Ok(vec) => Step::new_movement(vec),
+            Err(_) => return Err(ParseError::at_token(ln, num_str, "a smaller number")),
+        },
+        LEFT => Step::new_rotation(num.rem_euclid(360)),
+        RIGHT => Step::new_rotation(360 - num.rem_euclid(360)),
+        _ => Step::new_forward(num),
+    });
+}
+
+pub fn decode_steps(lines: &Vec<String>) -> Result<Vec<Step>, ParseError> {
+    let steps: Vec<Step> = lines.iter()
+        .enumerate()
+        .map(|(idx, ln)| decode_step(ln).map_err(|err| err.on_line(idx + 1)))
+        .collect::<Result<Vec<Step>, ParseError>>()?;
+    return Ok(steps);
+}
+
+pub fn rotate_ccw(start_dir: Vector2<i32>, degrees: Deg<i32>) -> Result<Vector2<i32>, Error> {
+    let basis: Basis2<f64> = Rotation2::from_angle(Deg(degrees.0 as f64));
+    let tmp_vec = Vector2{x: start_dir.x as f64, y: start_dir.y as f64};
+    let rot_vec = basis.rotate_vector(tmp_vec);
+    // `as` would clamp a turned i32::MIN rather than report it.
+    let to_i32 = |n: f64| -> Result<i32, Error> {
+        let n = n.round();
+        if n < i32::MIN as f64 || n > i32::MAX as f64 {
+            return Err(Error::Overflow(format!("the turned heading {} does not fit in i32", n)));
+        }
+        Ok(n as i32)
+    };
+    return Ok(Vector2{x: to_i32(rot_vec.x)?, y: to_i32(rot_vec.y)?});
+}
+
+// cgmath's operators wrap or panic on overflow, so headings and positions change through these.
+fn add_vec(a: Vector2<i32>, b: Vector2<i32>, what: &str) -> Result<Vector2<i32>, Error> {
+    return Ok(Vector2{x: checked_add(&a.x, &b.x, what)?, y: checked_add(&a.y, &b.y, what)?});
+}
+
+fn scale_vec(vec: Vector2<i32>, coeff: i32, what: &str) -> Result<Vector2<i32>, Error> {
+    return Ok(Vector2{x: checked_mul(&vec.x, &coeff, what)?, y: checked_mul(&vec.y, &coeff, what)?});
+}
+
+fn move_by(pos: Point2<i32>, vec: Vector2<i32>) -> Result<Point2<i32>, Error> {
+    let what = "the ship's position";
+    return Ok(Point2{x: checked_add(&pos.x, &vec.x, what)?, y: checked_add(&pos.y, &vec.y, what)?});
+}
+
+/// How far `pos` is from the origin along the axes.
+pub fn manhattan_distance(pos: Point2<i32>) -> Result<i32, Error> {
+    let what = "the distance from the start";
+    let x = checked_mul(&pos.x, &pos.x.signum(), what)?;
+    let y = checked_mul(&pos.y, &pos.y.signum(), what)?;
+    return checked_add(&x, &y, what);
+}
+
+pub fn exec_step(step: &Step, cur_dir: Vector2<i32>, pos: Point2<i32>) -> Result<(Vector2<i32>, Point2<i32>), Error> {
+    match step {
+        Step::Move{ vec } => Ok((cur_dir, move_by(pos, *vec)?)),
+        Step::Rotate{ deg } => Ok((rotate_ccw(cur_dir, *deg)?, pos)),
+        Step::Thrust{ coeff } => {
+            let new_step = Step::new_movement(scale_vec(cur_dir, *coeff, "the distance forward")?);
+            exec_step(&new_step, cur_dir, pos)
+        },
+    }
+}
+
+pub fn exec_steps(steps: &Vec<Step>) -> Result<(Vector2<i32>, Point2<i32>), Error> {
+    return exec_steps_traced(steps, exec_step, new_compass_vec(EAST, 1)?, &mut Vec::new());
+}
+
+/// Runs the steps with `exec` from `dir` at the origin, recording the heading
+/// and position after each as `[dir x, dir y, pos x, pos y]`.
+pub fn exec_steps_traced<F>(steps: &Vec<Step>, exec: F, start_dir: Vector2<i32>, track: &mut Vec<[i32; 4]>) -> Result<(Vector2<i32>, Point2<i32>), Error>
+    where F: Fn(&Step, Vector2<i32>, Point2<i32>) -> Result<(Vector2<i32>, Point2<i32>), Error>
+{
+    let mut dir = start_dir;
+    let mut pos = Point2{x: 0, y: 0};
+
+    for step in steps {
+        let (new_dir, new_pos) = exec(step, dir, pos)?;
+        trace!("({}, {}) ==> ({}, {}) heading ({}, {})", pos.x, pos.y, new_pos.x, new_pos.y, new_dir.x, new_dir.y);
+        pos = new_pos;
+        dir = new_dir;
+        track.push([dir.x, dir.y, pos.x, pos.y]);
+    }
+
+    return Ok((dir, pos));
+}
+
+pub fn exec_step2(step: &Step, cur_dir: Vector2<i32>, pos: Point2<i32>) -> Result<(Vector2<i32>, Point2<i32>), Error> {
+    match step {
+        Step::Move{ vec } => Ok((add_vec(cur_dir, *vec, "the waypoint")?, pos)),
+        Step::Rotate{ deg } => Ok((rotate_ccw(cur_dir, *deg)?, pos)),
+        Step::Thrust{ coeff } => {
+            let new_pos = move_by(pos, scale_vec(cur_dir, *coeff, "the distance to the waypoint")?)?;
+            Ok((cur_dir, new_pos))
+        },
+    }
+}
+
+pub fn exec_steps2(steps: &Vec<Step>) -> Result<(Vector2<i32>, Point2<i32>), Error> {
+    return exec_steps_traced(steps, exec_step2, Vector2{x: 10, y: 1}, &mut Vec::new());
+}
+
+// Turns by whole quarters on integers, rather than through `rotate_ccw`'s
+// floating point, so the checker does not share the solver's rounding.
+fn quarter_turns_ccw((x, y): (i64, i64), deg: i32) -> (i64, i64) {
+    let mut turned = (x, y);
+    for _ in 0..(deg / 90).rem_euclid(4) {
+        turned = (-turned.1, turned.0);
+    }
+    return turned;
+}
+
+/// Confirms that each entry of the certificate's `track` follows from the one
+/// before by the step between them, and that the last is `answer` from the origin.
+/// In part 1 the heading is the ship's own, in part 2 the waypoint.
+pub fn check_track(steps: &Vec<Step>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let track: Vec<[i64; 4]> = certificate_field(certificate, "track")?;
+    if track.len() != steps.len() {
+        return Err(Error::Certificate(format!("the track has {} entries for {} steps", track.len(), steps.len())));
+    }
+    let mut dir: (i64, i64) = if part == 1 { (1, 0) } else { (10, 1) };
+    let mut pos: (i64, i64) = (0, 0);
+    for (idx, (step, entry)) in steps.iter().zip(&track).enumerate() {
+        match step {
+            Step::Move{ vec } if part == 1 => pos = (pos.0 + vec.x as i64, pos.1 + vec.y as i64),
+            Step::Move{ vec } => dir = (dir.0 + vec.x as i64, dir.1 + vec.y as i64),
+            Step::Rotate{ deg } => dir = quarter_turns_ccw(dir, deg.0),
+            Step::Thrust{ coeff } => pos = (pos.0 + dir.0 * *coeff as i64, pos.1 + dir.1 * *coeff as i64),
+        }
+        if [dir.0, dir.1, pos.0, pos.1] != *entry {
+            return Err(Error::Certificate(format!("step {} leads to heading ({}, {}) at ({}, {})", idx, dir.0, dir.1, pos.0, pos.1)));
+        }
+    }
+    check_answer(pos.0.abs() + pos.1.abs(), answer)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Step>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(text: &str) -> Result<Vec<Step>, Error> {
+        let lines = to_lines(text);
+        Ok(decode_steps(&lines)?)
+    }
+
+    const VERSION: u32 = 3;
+
+    fn part1(steps: &Vec<Step>) -> Result<Certified<i32>, Error> {
+        let mut track = Vec::new();
+        let (_, pos) = exec_steps_traced(steps, exec_step, new_compass_vec(EAST, 1)?, &mut track)?;
+        Ok(Certified::new(manhattan_distance(pos)?, json!({ "track": track })))
+    }
+
+    fn part2(steps: &Vec<Step>) -> Result<Certified<i32>, Error> {
+        let mut track = Vec::new();
+        let (_, pos) = exec_steps_traced(steps, exec_step2, Vector2{x: 10, y: 1}, &mut track)?;
+        Ok(Certified::new(manhattan_distance(pos)?, json!({ "track": track })))
+    }
+
+    fn details(steps: &Vec<Step>, part: u8) -> Result<Details, Error> {
+        // In part 2 the heading is the waypoint, relative to the ship.
+        let (dir, pos) = if part == 1 { exec_steps(steps)? } else { exec_steps2(steps)? };
+        let mut details = Details::new();
+        details.insert("heading".to_string(), json!({ "x": dir.x, "y": dir.y }));
+        details.insert("position".to_string(), json!({ "x": pos.x, "y": pos.y }));
+        Ok(details)
+    }
+
+    fn check(steps: &Vec<Step>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_track(steps, part, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        let lines = slurp_input("example").unwrap();
+        let steps: Vec<Step> = decode_steps(&lines).unwrap();
+        let (_dir, pos) = exec_steps(&steps).unwrap();
+        assert_eq!(pos.x.abs(), 17);
+        assert_eq!(pos.y.abs(), 8);
+        assert_eq!(pos.x.abs() + pos.y.abs(), 25);
+    }
+
+    #[test]
+    fn test2() {
+        let lines = slurp_input("example").unwrap();
+        let steps: Vec<Step> = decode_steps(&lines).unwrap();
+        let (_dir, pos) = exec_steps2(&steps).unwrap();
+        assert_eq!(pos.x.abs(), 214);
+        assert_eq!(pos.y.abs(), 72);
+        assert_eq!(pos.x.abs() + pos.y.abs(), 286);
+    }
+
+    #[test]
+    fn test3() {
+        let lines = slurp_input("example2").unwrap();
+        let steps: Vec<Step> = decode_steps(&lines).unwrap();
+        let (_dir, pos) = exec_steps2(&steps).unwrap();
+        assert_eq!(pos.x.abs(), 214);
+        assert_eq!(pos.y.abs(), 72);
+        assert_eq!(pos.x.abs() + pos.y.abs(), 286);
+    }
+
+    #[test]
+    fn test_decode_step_errors() {
+        let err = decode_step(&"X10".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (1, Some("X10".to_string())));
+        let err = decode_step(&"F10 ".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (4, Some(" ".to_string())));
+        let err = decode_step(&"R45".to_string()).err().unwrap();
+        assert_eq!(err.expected, "a multiple of 90 degrees");
+        let err = decode_step(&"S-2147483648".to_string()).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (2, "a smaller number"));
+        assert!(decode_step(&"R-2147483610".to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let steps = Day12::parse("F2147483647\nF2147483647\n").unwrap();
+        assert!(matches!(Day12::part1(&steps), Err(Error::Overflow(_))));
+        let steps = Day12::parse("W2147483647\nF1\n").unwrap();
+        assert_eq!(Day12::part1(&steps).unwrap().answer, 2147483646);
+        let steps = Day12::parse("W2147483647\nW1\n").unwrap();
+        assert!(matches!(Day12::part1(&steps), Err(Error::Overflow(_))));
+        let steps = Day12::parse("S2147483647\nS2\nL90\n").unwrap();
+        assert!(matches!(Day12::part2(&steps), Err(Error::Overflow(_))));
+    }
+
+    #[test]
+    fn test_check_track() {
+        let steps = Day12::parse("F10\nN3\nF7\nR90\nF11\n").unwrap();
+        let solved = Day12::part2(&steps).unwrap();
+        assert_eq!(solved.certificate["track"][3], json!([4, -10, 170, 38]));
+        assert!(Day12::check(&steps, 2, &solved.certificate, "286").is_ok());
+
+        let err = Day12::check(&steps, 1, &solved.certificate, "25").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: step 0 leads to heading (1, 0) at (10, 0)");
+        let mut bent = solved.certificate.clone();
+        bent["track"][3] = json!([-4, 10, 170, 38]);
+        let err = Day12::check(&steps, 2, &bent, "286").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: step 3 leads to heading (4, -10) at (170, 38)");
+    }
+
+    #[test]
+    fn test_details() {
+        let lines = slurp_input("example").unwrap();
+        let steps: Vec<Step> = decode_steps(&lines).unwrap();
+        let details = Day12::details(&steps, 1).unwrap();
+        assert_eq!(details["heading"], json!({ "x": 0, "y": -1 }));
+        assert_eq!(details["position"], json!({ "x": 17, "y": -8 }));
+    }
+}
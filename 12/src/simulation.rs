@@ -0,0 +1,166 @@
+use cgmath::{Point2, Vector2};
+
+use aoc_common::{to_lines, Error, Grid, Simulation, Tile};
+
+use crate::{decode_steps, exec_step, exec_step2, Step};
+
+// The largest plot drawn. Rows are twice as tall as columns are wide, like terminal cells.
+const PLOT_WIDTH: f64 = 72.0;
+const PLOT_HEIGHT: f64 = 24.0;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Mark {
+    Sea,
+    Wake,
+    Start,
+    Ship,
+}
+
+impl Tile for Mark {
+    const EXPECTED: &'static str = "` `, `.`, `o` or `@`";
+
+    fn from_char(ch: char) -> Option<Mark> {
+        match ch {
+            ' ' => Some(Mark::Sea),
+            '.' => Some(Mark::Wake),
+            'o' => Some(Mark::Start),
+            '@' => Some(Mark::Ship),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Mark::Sea => ' ',
+            Mark::Wake => '.',
+            Mark::Start => 'o',
+            Mark::Ship => '@',
+        }
+    }
+}
+
+/// The ship following its instructions one at a time. In part 2 the heading is the waypoint.
+pub struct ShipSimulation {
+    steps: Vec<Step>,
+    waypoint: bool,
+    dir: Vector2<i32>,
+    path: Vec<Point2<i32>>,
+    /// Why the voyage stopped short, e.g. a step that overflowed.
+    fault: Option<Error>,
+}
+
+impl ShipSimulation {
+    pub fn new(steps: Vec<Step>, waypoint: bool) -> ShipSimulation {
+        let dir = if waypoint { Vector2{x: 10, y: 1} } else { Vector2{x: 1, y: 0} };
+        ShipSimulation{ steps: steps, waypoint: waypoint, dir: dir, path: vec![Point2{x: 0, y: 0}], fault: None }
+    }
+
+    fn pos(&self) -> Point2<i32> {
+        return *self.path.last().unwrap();
+    }
+}
+
+impl Simulation for ShipSimulation {
+    fn step(&mut self) -> bool {
+        let step = match self.steps.get(self.path.len() - 1) {
+            Some(step) if self.fault.is_none() => step,
+            _ => return false,
+        };
+        let moved = if self.waypoint { exec_step2(step, self.dir, self.pos()) } else { exec_step(step, self.dir, self.pos()) };
+        let (dir, pos) = match moved {
+            Ok(moved) => moved,
+            Err(err) => {
+                self.fault = Some(err);
+                return false;
+            },
+        };
+        self.dir = dir;
+        self.path.push(pos);
+        return true;
+    }
+
+    fn render(&self) -> String {
+        let min_x = self.path.iter().map(|p| p.x).min().unwrap() as f64;
+        let max_x = self.path.iter().map(|p| p.x).max().unwrap() as f64;
+        let min_y = self.path.iter().map(|p| p.y).min().unwrap() as f64;
+        let max_y = self.path.iter().map(|p| p.y).max().unwrap() as f64;
+        let scale = ((max_x - min_x) / (PLOT_WIDTH - 1.0)).max(2.0 * (max_y - min_y) / (PLOT_HEIGHT - 1.0)).max(1.0);
+        let cell = |p: &Point2<i32>| -> (usize, usize) {
+            let col = ((p.x as f64 - min_x) / scale).round() as usize;
+            let row = ((max_y - p.y as f64) / (2.0 * scale)).round() as usize;
+            (col, row)
+        };
+
+        let (width, height) = cell(&Point2{x: max_x as i32, y: min_y as i32});
+        let mut plot = Grid::new(width + 1, height + 1, Mark::Sea);
+        for leg in self.path.windows(2) {
+            let (from, to) = (cell(&leg[0]), cell(&leg[1]));
+            let n = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)).max(1);
+            for i in 0..=n {
+                let col = from.0 as f64 + (to.0 as f64 - from.0 as f64) * i as f64 / n as f64;
+                let row = from.1 as f64 + (to.1 as f64 - from.1 as f64) * i as f64 / n as f64;
+                plot[(col.round() as usize, row.round() as usize)] = Mark::Wake;
+            }
+        }
+        plot[cell(&self.path[0])] = Mark::Start;
+        plot[cell(&self.pos())] = Mark::Ship;
+        return plot.to_string();
+    }
+
+    fn status(&self) -> String {
+        let pos = self.pos();
+        let heading = if self.waypoint { "waypoint" } else { "heading" };
+        let distance = (pos.x as i64).abs() + (pos.y as i64).abs();
+        let status = format!(
+            "step {}/{}: at ({}, {}), {} ({}, {}), distance {}",
+            self.path.len() - 1, self.steps.len(), pos.x, pos.y, heading, self.dir.x, self.dir.y, distance
+        );
+        return match &self.fault {
+            Some(err) => format!("{}, stopped: {}", status, err),
+            None => status,
+        };
+    }
+}
+
+/// The ship's voyage in the given part, following the instructions in `text`.
+pub fn simulate(text: &str, part: u8) -> Result<Box<dyn Simulation>, Error> {
+    let steps = decode_steps(&to_lines(text))?;
+    return match part {
+        1 => Ok(Box::new(ShipSimulation::new(steps, false))),
+        2 => Ok(Box::new(ShipSimulation::new(steps, true))),
+        _ => Err(Error::Unsolved),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voyage() {
+        let mut sim = simulate("F10\nN3\nF7\nR90\nF11\n", 1).unwrap();
+        assert_eq!(sim.render(), "@\n");
+        while sim.step() {}
+        assert_eq!(sim.status(), "step 5/5: at (17, -8), heading (0, -1), distance 25");
+
+        let plot: Vec<String> = sim.render().lines().map(|ln| ln.trim_end().to_string()).collect();
+        let expected = vec![
+            "          ........",
+            "          .      .",
+            "o..........      .",
+            "                 .",
+            "                 .",
+            "                 .",
+            "                 @",
+        ];
+        assert_eq!(plot, expected);
+    }
+
+    #[test]
+    fn test_voyage_overflow() {
+        let mut sim = simulate("F2147483647\nF2147483647\nN1\n", 1).unwrap();
+        while sim.step() {}
+        assert_eq!(sim.status(), "step 1/3: at (2147483647, 0), heading (1, 0), distance 2147483647, stopped: arithmetic overflow: the ship's position does not fit in i32");
+    }
+
+}
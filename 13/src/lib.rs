@@ -0,0 +1,342 @@
+use num::Integer;
+use log::debug;
+
+use aoc_common::{certificate_field, checked_add, checked_mul, json, parse_comma_list, parse_integer, read_parsed, Certificate, Certified, Error, InputSource, Iterations, ParseError, Solution};
+
+#[cfg(not(feature = "bigint"))]
+pub type Time = u64;
+#[cfg(feature = "bigint")]
+pub type Time = aoc_common::BigUint;
+
+// Part 1: earliest valid departure time: 4938
+// Part 2: earliest aligned departure: 230903629977901
+
+pub fn slurp_input1<S: Into<InputSource>>(source: S) -> Result<(u32, Vec<u32>), Error> {
+    return read_parsed(source, parse_input1);
+}
+
+// The second line lists bus IDs, with `x` for buses that are out of service.
+fn parse_bus_line(contents: &str) -> Result<Vec<Option<u32>>, ParseError> {
+    let ln = match contents.lines().nth(1) {
+        Some(ln) => ln,
+        None => return Err(ParseError::at(contents, contents.len(), "bus IDs on the second line")),
+    };
+
+    let buses = parse_comma_list(ln, |s| {
+        if s == "x" {
+            return Ok(None);
+        }
+        let bus_id: u32 = parse_integer(ln, s)?;
+        if bus_id == 0 {
+            return Err(ParseError::at_token(ln, s, "a non-zero bus ID"));
+        }
+        Ok(Some(bus_id))
+    });
+    return buses.map_err(|err| err.on_line(2));
+}
+
+pub fn parse_input1(contents: &str) -> Result<(u32, Vec<u32>), ParseError> {
+    let first_ln = contents.lines().next().unwrap_or("");
+    let arrival_time: u32 = parse_integer(first_ln, first_ln)?;
+
+    let bus_times: Vec<u32> = parse_bus_line(contents)?
+        .into_iter()
+        .flatten()
+        .collect();
+    return Ok((arrival_time, bus_times));
+}
+
+pub struct BusSched {
+    pub bus_id: u32,
+    pub offset: u32,
+}
+
+pub fn slurp_input2<S: Into<InputSource>>(source: S) -> Result<Vec<BusSched>, Error> {
+    return read_parsed(source, parse_input2);
+}
+
+pub fn parse_input2(contents: &str) -> Result<Vec<BusSched>, ParseError> {
+    let bus_times: Vec<BusSched> = parse_bus_line(contents)?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, bus_id)| {
+            bus_id.map(|bus_id| BusSched{ bus_id: bus_id, offset: idx as u32  })
+        })
+        .collect();
+    return Ok(bus_times);
+}
+
+// A departure can be later than any u32 time, but the next one after a u32 arrival fits in a u64.
+pub fn next_departure(arrival_time: u32, bus: u32) -> u64 {
+    return (arrival_time as u64).div_ceil(bus as u64) * bus as u64;
+}
+
+pub fn find_earliest_valid_departure(arrival_time: u32, departure_times: &Vec<u32>) -> Result<(u32, u64), Error> {
+    return departure_times.iter()
+        .map(|n| (*n, next_departure(arrival_time, *n)))
+        .min_by(|a, b| a.1.cmp(&b.1))
+        .ok_or_else(|| Error::NoAnswer("every bus is out of service".to_string()));
+}
+
+/// The wait for the bus leaving at `departure` times its ID.
+pub fn wait_times_bus(arrival_time: u32, bus: u32, departure: u64) -> Result<u64, Error> {
+    let wait = departure.checked_sub(arrival_time as u64)
+        .ok_or_else(|| Error::NoAnswer(format!("bus {} leaves at {}, before arriving at {}", bus, departure, arrival_time)))?;
+    return checked_mul(&wait, &(bus as u64), "the wait times the bus ID");
+}
+
+pub fn find_earliest_solution(bus_scheds: &Vec<BusSched>) -> Result<Time, Error> {
+    let what = "the earliest aligned departure";
+    let mut t = Time::from(0_u32);
+    let first = bus_scheds.first().ok_or_else(|| Error::NoAnswer("every bus is out of service".to_string()))?;
+    let mut step = Time::from(first.bus_id);
+    // Buses whose IDs share a factor may never line up, leaving only cancellation to stop the search.
+    let mut iterations = Iterations::checking_every(1024);
+
+    for (idx, nth_sched) in bus_scheds.iter().enumerate() {
+        let bus_id = Time::from(nth_sched.bus_id);
+        let offset = Time::from(nth_sched.offset);
+        while !Integer::is_multiple_of(&checked_add(&t, &offset, what)?, &bus_id) {
+            iterations.tick()?;
+            t = checked_add(&t, &step, what)?;
+        }
+        debug!("t = {}", t);
+        // The last step is never used, and could overflow where the answer does not.
+        if idx + 1 < bus_scheds.len() {
+            let base = Integer::div_floor(&step, &step.gcd(&bus_id));
+            step = checked_mul(&base, &bus_id, what)?;
+            debug!("step = {}", step);
+        }
+    }
+
+    return Ok(t);
+}
+
+// The bus must leave at `departure`, no other bus may leave between arriving and then,
+// and the wait times the bus ID must be `answer`.
+pub fn check_departure(arrival_time: u32, bus_times: &Vec<u32>, bus: u32, departure: u64, answer: &str) -> Result<(), Error> {
+    if !bus_times.contains(&bus) {
+        return Err(Error::Certificate(format!("bus {} is not in service", bus)));
+    }
+    if !Integer::is_multiple_of(&departure, &(bus as u64)) || departure < arrival_time as u64 {
+        return Err(Error::Certificate(format!("bus {} does not leave at {} after arriving at {}", bus, departure, arrival_time)));
+    }
+    for other in bus_times {
+        let next_dep = next_departure(arrival_time, *other);
+        if next_dep < departure {
+            return Err(Error::Certificate(format!("bus {} leaves earlier, at {}", other, next_dep)));
+        }
+    }
+    let product = wait_times_bus(arrival_time, bus, departure)?;
+    if product.to_string() != answer {
+        return Err(Error::Certificate(format!("waiting for bus {} gives {}", bus, product)));
+    }
+    return Ok(());
+}
+
+// Every bus must leave its offset after `t`. The departures repeat every lcm of the
+// bus IDs, so a `t` before then is also the earliest.
+pub fn check_aligned(bus_scheds: &Vec<BusSched>, t: &Time, answer: &str) -> Result<(), Error> {
+    for sched in bus_scheds {
+        let dep = checked_add(t, &Time::from(sched.offset), "the departure")?;
+        if !Integer::is_multiple_of(&dep, &Time::from(sched.bus_id)) {
+            return Err(Error::Certificate(format!("bus {} does not leave at {}", sched.bus_id, dep)));
+        }
+    }
+
+    let mut period = Time::from(1_u32);
+    for sched in bus_scheds {
+        let bus_id = Time::from(sched.bus_id);
+        let base = Integer::div_floor(&period, &period.gcd(&bus_id));
+        match checked_mul(&base, &bus_id, "the period") {
+            Ok(next) => period = next,
+            // A period too large to hold is later than any `t`.
+            Err(_) => return check_answer(t, answer),
+        }
+    }
+    if *t >= period {
+        return Err(Error::Certificate(format!("the departures already lined up {} minutes earlier", period)));
+    }
+    return check_answer(t, answer);
+}
+
+fn check_answer(t: &Time, answer: &str) -> Result<(), Error> {
+    if t.to_string() != answer {
+        return Err(Error::Certificate(format!("the timestamp is {}", t)));
+    }
+    return Ok(());
+}
+
+pub struct Notes {
+    pub arrival_time: u32,
+    pub bus_times: Vec<u32>,
+    pub bus_scheds: Vec<BusSched>,
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type Answer1 = u64;
+    type Answer2 = Time;
+
+    const VERSION: u32 = 4;
+
+    fn parse(text: &str) -> Result<Notes, Error> {
+        let (arrival_time, bus_times) = parse_input1(text)?;
+        Ok(Notes{
+            arrival_time: arrival_time,
+            bus_times: bus_times,
+            bus_scheds: parse_input2(text)?,
+        })
+    }
+
+    fn part1(notes: &Notes) -> Result<Certified<u64>, Error> {
+        let (id, dep_time) = find_earliest_valid_departure(notes.arrival_time, &notes.bus_times)?;
+        Ok(Certified::new(wait_times_bus(notes.arrival_time, id, dep_time)?, json!({ "bus": id, "departure": dep_time })))
+    }
+
+    fn part2(notes: &Notes) -> Result<Certified<Time>, Error> {
+        let t = find_earliest_solution(&notes.bus_scheds)?;
+        // A string, as the timestamp may not fit in a JSON number.
+        let certificate = json!({ "timestamp": t.to_string() });
+        Ok(Certified::new(t, certificate))
+    }
+
+    fn check(notes: &Notes, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        if part == 1 {
+            let bus = certificate_field(certificate, "bus")?;
+            let departure = certificate_field(certificate, "departure")?;
+            return check_departure(notes.arrival_time, &notes.bus_times, bus, departure, answer);
+        }
+        let timestamp: String = certificate_field(certificate, "timestamp")?;
+        let t: Time = timestamp.parse().map_err(|_| Error::Certificate(format!("`{}` is not a timestamp", timestamp)))?;
+        check_aligned(&notes.bus_scheds, &t, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use aoc_common::CancelToken;
+
+    #[test]
+    fn test_example1() {
+        let (arrival, bus_times) = slurp_input1("example").unwrap();
+        let (id, dep_time) = find_earliest_valid_departure(arrival, &bus_times).unwrap();
+        assert_eq!(arrival, 939);
+        assert_eq!(dep_time, 944);
+        let wait = dep_time - arrival as u64;
+        assert_eq!(wait, 5);
+        let answer = wait_times_bus(arrival, id, dep_time).unwrap();
+        assert_eq!(answer, 295);
+    }
+
+    #[test]
+    fn test_departure_after_u32() {
+        let notes = Day13::parse("4294967295\n4294967291\n").unwrap();
+        let solved = Day13::part1(&notes).unwrap();
+        assert_eq!(solved.answer, 4294967287 * 4294967291);
+        assert_eq!(solved.certificate, json!({ "bus": 4294967291_u32, "departure": 8589934582_u64 }));
+        assert!(Day13::check(&notes, 1, &solved.certificate, &solved.answer.to_string()).is_ok());
+
+        let notes = Day13::parse("4294967000\n4294967291\n").unwrap();
+        assert_eq!(Day13::part1(&notes).unwrap().answer, 291 * 4294967291);
+    }
+
+    #[test]
+    fn test_example2() {
+        let bus_scheds = slurp_input2("example").unwrap();
+        assert_eq!(bus_scheds.len(), 5);
+        assert_eq!(bus_scheds[0].bus_id, 7);
+        assert_eq!(bus_scheds[0].offset, 0);
+        assert_eq!(bus_scheds[bus_scheds.len() - 1].bus_id, 19);
+        assert_eq!(bus_scheds[bus_scheds.len() - 1].offset, 7);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_input2("939\n7,13,?,59").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 6));
+        let err = parse_input1("939").err().unwrap();
+        assert_eq!((err.line, err.expected.as_str()), (1, "bus IDs on the second line"));
+    }
+
+    #[test]
+    fn test_solver2_1() {
+        let bus_scheds = slurp_input2("example").unwrap();
+        let solution = find_earliest_solution(&bus_scheds).unwrap();
+        assert_eq!(solution, Time::from(1068781_u32));
+    }
+
+    #[test]
+    fn test_solver2_2() {
+        let bus_scheds = slurp_input2("example2").unwrap();
+        let solution = find_earliest_solution(&bus_scheds).unwrap();
+        assert_eq!(solution, Time::from(3417_u32));
+    }
+
+    #[test]
+    fn test_solver2_3() {
+        let bus_scheds = slurp_input2("example3").unwrap();
+        let solution = find_earliest_solution(&bus_scheds).unwrap();
+        assert_eq!(solution, Time::from(754018_u32));
+    }
+
+    #[test]
+    fn test_solver2_4() {
+        let bus_scheds = slurp_input2("example4").unwrap();
+        let solution = find_earliest_solution(&bus_scheds).unwrap();
+        assert_eq!(solution, Time::from(779210_u32));
+    }
+
+    #[test]
+    fn test_check_certificates() {
+        let notes = Day13::parse("939\n7,13,x,x,59,x,31,19\n").unwrap();
+        let certificate = Day13::part1(&notes).unwrap().certificate;
+        assert_eq!(certificate, json!({ "bus": 59, "departure": 944 }));
+        assert!(Day13::check(&notes, 1, &certificate, "295").is_ok());
+        let err = Day13::check(&notes, 1, &json!({ "bus": 7, "departure": 945 }), "42").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: bus 59 leaves earlier, at 944");
+
+        let certificate = Day13::part2(&notes).unwrap().certificate;
+        assert_eq!(certificate, json!({ "timestamp": "1068781" }));
+        assert!(Day13::check(&notes, 2, &certificate, "1068781").is_ok());
+        // The same alignment, one period later.
+        let later = (1068781_u64 + 7 * 13 * 59 * 31 * 19).to_string();
+        let err = Day13::check(&notes, 2, &json!({ "timestamp": later }), &later).unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the departures already lined up 3162341 minutes earlier");
+        let err = Day13::check(&notes, 2, &json!({ "timestamp": "1068782" }), "1068782").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: bus 7 does not leave at 1068782");
+    }
+
+    #[test]
+    fn test_every_bus_out_of_service() {
+        let notes = Day13::parse("939\nx,x\n").unwrap();
+        let err = Day13::part1(&notes).err().unwrap();
+        assert_eq!(err.to_string(), "no answer: every bus is out of service");
+        let err = Day13::part2(&notes).err().unwrap();
+        assert_eq!(err.to_string(), "no answer: every bus is out of service");
+    }
+
+    #[test]
+    fn test_solver2_never_aligned() {
+        let bus_scheds = parse_input2("0\n2,4\n").unwrap();
+        let token = CancelToken::with_timeout(Duration::ZERO);
+        let err = token.run(|| find_earliest_solution(&bus_scheds)).unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 1024 iterations");
+    }
+
+    #[test]
+    fn test_solver2_overflow() {
+        let bus_scheds = parse_input2("0\n131009,131011,131023,131041\n").unwrap();
+        let solution = find_earliest_solution(&bus_scheds);
+        #[cfg(not(feature = "bigint"))]
+        assert!(matches!(solution, Err(Error::Overflow(_))));
+        #[cfg(feature = "bigint")]
+        assert_eq!(solution.unwrap().to_string(), "68144350891483101411");
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::{BTreeMap, HashMap};
+
+use aoc_common::combinator::{alt, cut, end_of_line, expect, literal, map, parse_line, preceded, separated_pair, take_while, terminated, unsigned, verified};
+use aoc_common::{certificate_field, check_answer, count, json, read_lines, to_lines, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+// Part 1: sum = 13476250121721
+// Part 2: sum = 4463708436768
+
+#[derive(Debug,Eq,PartialEq)]
+pub enum Instr {
+    SetMask{ mask_str: String, and_mask: u64, or_mask: u64, },
+    SetMem{ addr: u64, val: u64 },
+}
+
+impl Instr {
+    pub fn new_and_mask(mask_str: &str) -> u64 {
+        let mut mask: u64 = u64::MAX;
+        for (idx, ch) in mask_str.chars().rev().enumerate() {
+            if ch == '0' {
+                let update_mask = (u64::MAX - 1).rotate_left(idx as u32) & (2_u64.pow(36) - 1);
+                mask = mask & update_mask;
+            }
+        }
+        return mask;
+    }
+
+    pub fn new_or_mask(mask_str: &str) -> u64 {
+        let mut mask: u64 = 0;
+        for (idx, ch) in mask_str.chars().rev().enumerate() {
+            if ch == '1' {
+                let update_mask = 1_u64.rotate_left(idx as u32);
+                mask = mask | update_mask;
+            }
+        }
+        return mask;
+    }
+
+    pub fn new_set_mask(mask_str: &str) -> Instr {
+        Instr::SetMask{
+            mask_str: mask_str.to_string(),
+            and_mask: Instr::new_and_mask(mask_str),
+            or_mask: Instr::new_or_mask(mask_str),
+        }
+    }
+}
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    return read_lines(source);
+}
+
+pub fn parse_instr(ln: &String) -> Result<Instr, ParseError> {
+    let bits = terminated(take_while(|ch| matches!(ch, 'X' | '0' | '1')), expect("`X`, `0` or `1`", end_of_line()));
+    let set_mask = map(
+        preceded(literal("mask = "), cut(verified("a 36 bit mask", bits, |mask: &&str| mask.len() == 36))),
+        Instr::new_set_mask,
+    );
+    let set_mem = map(
+        preceded(literal("mem["), cut(separated_pair(unsigned(), literal("] = "), unsigned()))),
+        |(addr, val)| Instr::SetMem{ addr: addr, val: val },
+    );
+    return parse_line(ln, expect("`mask = ...` or `mem[...] = ...`", alt((set_mask, set_mem))));
+}
+
+pub fn parse_program(lines: &Vec<String>) -> Result<Vec<Instr>, ParseError> {
+    lines.iter()
+        .enumerate()
+        .map(|(idx, ln)| parse_instr(ln).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+pub fn run_program_v1(program: &Vec<Instr>) -> HashMap<u64, u64> {
+    return run_program_v1_traced(program, &mut HashMap::new());
+}
+
+/// Runs the program like `run_program_v1`, recording which instruction last wrote each address.
+pub fn run_program_v1_traced(program: &Vec<Instr>, writers: &mut HashMap<u64, usize>) -> HashMap<u64, u64> {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    let mut and_mask: u64 = 0;
+    let mut or_mask: u64 = 0;
+    for (idx, instr) in program.iter().enumerate() {
+        match instr {
+            Instr::SetMask{ and_mask: and_mask1, or_mask: or_mask1, .. } => {
+                and_mask = *and_mask1;
+                or_mask = *or_mask1;
+            },
+            Instr::SetMem{ addr, val, } => {
+                let modified_val = val & and_mask | or_mask;
+                mem.insert(*addr, modified_val);
+                writers.insert(*addr, idx);
+            }
+        }
+    }
+    return mem;
+}
+
+pub fn x_mask_for_addr(mask: &String, addr: u64) -> Vec<char> {
+    let addr1 = addr | Instr::new_or_mask(mask);
+
+    let padded_mask = format!("{:0>36}", mask);
+    let mask_chars = padded_mask.chars();
+    let addr1_bin = format!("{:036b}", addr1);
+    let addr1_bin_chars = addr1_bin.chars();
+
+    let mut new_chars: Vec<char> = vec![];
+    for (ac, mc) in addr1_bin_chars.zip(mask_chars) {
+        if mc == '0' {
+            new_chars.push(ac);
+        } else if mc == '1' {
+            new_chars.push('1');
+        } else if mc == 'X' {
+            new_chars.push('X');
+        }
+    }
+
+    return new_chars;
+}
+
+pub fn enumerate_addresses(mask: &String, addr: u64) -> Vec<u64> {
+    let x_mask = x_mask_for_addr(mask, addr);
+    let mut accum = vec![];
+    enumerate_addresses_rec(&x_mask, 0, 0, &mut accum);
+    return accum;
+}
+
+pub fn enumerate_addresses_rec(x_mask: &Vec<char>, pos: usize, num: u64, accum: &mut Vec<u64>) {
+    if pos == x_mask.len() {
+        let num_copy: u64 = num;
+        accum.push(num_copy);
+        return;
+    }
+
+    if x_mask[pos] == 'X' || x_mask[pos] == '0' {
+        let new_num0 = num << 1;
+        enumerate_addresses_rec(x_mask, pos + 1, new_num0, accum);
+    }
+
+    if x_mask[pos] == 'X' || x_mask[pos] == '1' {
+        let new_num1 = num << 1 | 1;
+        enumerate_addresses_rec(x_mask, pos + 1, new_num1, accum);
+    }
+}
+
+pub fn run_program_v2(program: &Vec<Instr>) -> HashMap<u64, u64> {
+    return run_program_v2_traced(program, &mut HashMap::new());
+}
+
+/// Runs the program like `run_program_v2`, recording which instruction last wrote each address.
+pub fn run_program_v2_traced(program: &Vec<Instr>, writers: &mut HashMap<u64, usize>) -> HashMap<u64, u64> {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    let mut mask: String = String::new();
+    for (idx, instr) in program.iter().enumerate() {
+        match instr {
+            Instr::SetMask{ mask_str: mask_str1, ..} => {
+                mask = mask_str1.clone();
+            },
+            Instr::SetMem{ addr, val, } => {
+                let addrs = enumerate_addresses(&mask, *addr);
+                count("addresses_written", addrs.len() as u64);
+                for addr in addrs {
+                    mem.insert(addr, *val);
+                    writers.insert(addr, idx);
+                }
+            }
+        }
+    }
+    return mem;
+}
+
+fn certify_writers(mem: HashMap<u64, u64>, writers: HashMap<u64, usize>) -> Certified<u64> {
+    let sorted: BTreeMap<u64, usize> = writers.into_iter().collect();
+    let pairs: Vec<(u64, usize)> = sorted.into_iter().collect();
+    return Certified::new(mem.values().sum(), json!({ "writers": pairs }));
+}
+
+// Where a write of `val` to `addr` lands under `mask`, and what it leaves there,
+// worked out bit by bit from the mask's characters rather than the precomputed masks.
+fn writes_under(mask: &str, part: u8, addr: u64, val: u64) -> (Vec<u64>, u64) {
+    let mut addrs: Vec<u64> = vec![0];
+    let mut value: u64 = 0;
+    for (bit, ch) in mask.chars().rev().enumerate() {
+        let on = 1_u64 << bit;
+        if part == 1 {
+            let set = match ch { '1' => true, '0' => false, _ => val & on != 0 };
+            if set {
+                value = value | on;
+            }
+            continue;
+        }
+        match ch {
+            '0' => addrs.iter_mut().for_each(|a| *a = *a | (addr & on)),
+            '1' => addrs.iter_mut().for_each(|a| *a = *a | on),
+            _ => addrs = addrs.iter().flat_map(|a| [*a, *a | on]).collect(),
+        }
+    }
+    if part == 1 {
+        return (vec![addr], value);
+    }
+    return (addrs, val);
+}
+
+/// Confirms that the certificate's `writers` name, for every address the program
+/// writes, the last instruction to write it, and that the values they leave add up to `answer`.
+pub fn check_writers(program: &Vec<Instr>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let pairs: Vec<(u64, usize)> = certificate_field(certificate, "writers")?;
+    let mut writers: HashMap<u64, usize> = HashMap::new();
+    for (addr, idx) in pairs {
+        if writers.insert(addr, idx).is_some() {
+            return Err(Error::Certificate(format!("address {} has two writers", addr)));
+        }
+    }
+    let mut mask = "X".repeat(36);
+    let mut claimed = 0;
+    let mut total: u128 = 0;
+    for (idx, instr) in program.iter().enumerate() {
+        let (addr, val) = match instr {
+            Instr::SetMask{ mask_str, .. } => {
+                mask = mask_str.clone();
+                continue;
+            },
+            Instr::SetMem{ addr, val } => (*addr, *val),
+        };
+        let (addrs, value) = writes_under(&mask, part, addr, val);
+        for written in addrs {
+            match writers.get(&written) {
+                Some(&last) if last == idx => {
+                    claimed = claimed + 1;
+                    total = total + value as u128;
+                },
+                Some(&last) if last > idx => {},
+                _ => return Err(Error::Certificate(format!("instruction {} writes address {} after its listed writer", idx, written))),
+            }
+        }
+    }
+    if claimed != writers.len() {
+        return Err(Error::Certificate("a listed writer does not write its address".to_string()));
+    }
+    check_answer(total, answer)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instr>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(text: &str) -> Result<Vec<Instr>, Error> {
+        let lines = to_lines(text);
+        Ok(parse_program(&lines)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(instructions: &Vec<Instr>) -> Result<Certified<u64>, Error> {
+        let mut writers = HashMap::new();
+        let mem = run_program_v1_traced(instructions, &mut writers);
+        Ok(certify_writers(mem, writers))
+    }
+
+    fn part2(instructions: &Vec<Instr>) -> Result<Certified<u64>, Error> {
+        let mut writers = HashMap::new();
+        let mem = run_program_v2_traced(instructions, &mut writers);
+        Ok(certify_writers(mem, writers))
+    }
+
+    fn check(instructions: &Vec<Instr>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_writers(instructions, part, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instr_set_mask_and() {
+        let mask = Instr::new_and_mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        assert_eq!(mask, 0b111111111111111111111111111111111101);
+    }
+
+    #[test]
+    fn test_instr_set_mask_or() {
+        let mask = Instr::new_or_mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        assert_eq!(mask, 0b000000000000000000000000000001000000);
+    }
+
+    #[test]
+    fn test_example1() {
+        let lines = slurp_input("example").unwrap();
+        let instructions: Vec<Instr> = parse_program(&lines).unwrap();
+        assert_eq!(instructions[0], Instr::new_set_mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"));
+        assert_eq!(instructions[1], Instr::SetMem{ addr: 8, val: 11 });
+        assert_eq!(instructions[2], Instr::SetMem{ addr: 7, val: 101 });
+
+        let mem1 = run_program_v1(&instructions);
+        for (k, v) in mem1.iter() {
+            println!("mem[{}] = {}", k, v);
+        }
+        assert_eq!(mem1.get(&7_u64).unwrap(), &101_u64);
+        assert_eq!(mem1.get(&8_u64).unwrap(), &64_u64);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let x: u8 = u8::MAX - 1;
+        assert_eq!(x, 0b11111110);
+        assert_eq!(x.rotate_left(1), 0b11111101);
+    }
+
+    #[test]
+    fn test_enumerate_addresses_rec1() {
+        let x_mask: Vec<char> = "X1101X".chars().collect();
+        let mut addrs: Vec<u64> = vec![];
+        enumerate_addresses_rec(&x_mask, 0, 0, &mut addrs);
+        addrs.sort();
+        assert_eq!(addrs.len(), 4);
+        assert_eq!(addrs[0], 26);
+        assert_eq!(addrs[1], 27);
+        assert_eq!(addrs[2], 58);
+        assert_eq!(addrs[3], 59);
+    }
+
+    #[test]
+    fn test_enumerate_addresses_rec2() {
+        let x_mask: Vec<char> = "1X0XX".chars().collect();
+        let mut addrs: Vec<u64> = vec![];
+        enumerate_addresses_rec(&x_mask, 0, 0, &mut addrs);
+        addrs.sort();
+        assert_eq!(addrs.len(), 8);
+        assert_eq!(addrs[0], 16);
+        assert_eq!(addrs[1], 17);
+        assert_eq!(addrs[2], 18);
+        assert_eq!(addrs[3], 19);
+        assert_eq!(addrs[4], 24);
+        assert_eq!(addrs[5], 25);
+        assert_eq!(addrs[6], 26);
+        assert_eq!(addrs[7], 27);
+    }
+
+    #[test]
+    fn test_x_mask_for_addr() {
+        let mask = "X1001X".to_string();
+        let addr = 42;
+        let x_mask: Vec<char> = x_mask_for_addr(&mask, addr);
+        println!("x_mask = {:?}", x_mask);
+        assert_eq!(x_mask.len(), 36);
+        assert_eq!(x_mask[30], 'X');
+        assert_eq!(x_mask[31], '1');
+        assert_eq!(x_mask[32], '1');
+        assert_eq!(x_mask[33], '0');
+        assert_eq!(x_mask[34], '1');
+        assert_eq!(x_mask[35], 'X');
+    }
+
+    #[test]
+    fn test_example2() {
+        let lines = slurp_input("example2").unwrap();
+        let instructions: Vec<Instr> = parse_program(&lines).unwrap();
+        assert_eq!(instructions[0], Instr::new_set_mask("000000000000000000000000000000X1001X"));
+        assert_eq!(instructions[1], Instr::SetMem{ addr: 42, val: 100 });
+        assert_eq!(instructions[2], Instr::new_set_mask("00000000000000000000000000000000X0XX"));
+        assert_eq!(instructions[3], Instr::SetMem{ addr: 26, val: 1 });
+
+        let mem1 = run_program_v2(&instructions);
+        assert_eq!(mem1.get(&58_u64).unwrap(), &100_u64);
+        assert_eq!(mem1.get(&59_u64).unwrap(), &100_u64);
+        assert_eq!(mem1.get(&24_u64).unwrap(), &1_u64);
+        assert_eq!(mem1.get(&25_u64).unwrap(), &1_u64);
+        assert_eq!(mem1.get(&26_u64).unwrap(), &1_u64);
+        assert_eq!(mem1.get(&27_u64).unwrap(), &1_u64);
+    }
+
+    #[test]
+    fn test_check_writers() {
+        let instructions = Day14::parse("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] = 101\nmem[8] = 0\n").unwrap();
+        let solved = Day14::part1(&instructions).unwrap();
+        assert_eq!(solved.certificate, json!({ "writers": [[7, 2], [8, 3]] }));
+        assert!(Day14::check(&instructions, 1, &solved.certificate, "165").is_ok());
+
+        let err = Day14::check(&instructions, 1, &json!({ "writers": [[7, 2], [8, 1]] }), "174").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: instruction 3 writes address 8 after its listed writer");
+        let err = Day14::check(&instructions, 1, &json!({ "writers": [[7, 2], [8, 3], [9, 3]] }), "165").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: a listed writer does not write its address");
+
+        let instructions = Day14::parse(&slurp_input("example2").unwrap().join("\n")).unwrap();
+        let solved = Day14::part2(&instructions).unwrap();
+        assert!(Day14::check(&instructions, 2, &solved.certificate, "208").is_ok());
+        let err = Day14::check(&instructions, 1, &solved.certificate, "208").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: instruction 1 writes address 42 after its listed writer");
+    }
+
+    #[test]
+    fn test_parse_instr_errors() {
+        let err = parse_instr(&"mem[8] = 11 ".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (12, Some(" ".to_string())));
+        let err = parse_instr(&"mask = XX2".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (10, Some("2".to_string())));
+        assert_eq!(err.expected, "`X`, `0` or `1`");
+        let err = parse_instr(&"mask = XX".to_string()).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (8, "a 36 bit mask"));
+        let err = parse_instr(&"mem(8) = 11".to_string()).err().unwrap();
+        assert_eq!(err.column, 1);
+        let err = parse_instr(&"mem[8]= 11".to_string()).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (6, "`] = `"));
+    }
+}
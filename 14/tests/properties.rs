@@ -0,0 +1,27 @@
+use aoc14::{x_mask_for_addr, Instr};
+use proptest::prelude::*;
+
+proptest! {
+    // A `0` in the mask leaves the address bit alone and clears the value bit, a `1`
+    // sets both, and an `X` leaves the value bit alone but floats the address bit.
+    #[test]
+    fn value_masks_agree_with_address_mask(mask in "[01X]{36}", addr in 0_u64..(1 << 36)) {
+        let and_mask = Instr::new_and_mask(&mask);
+        let or_mask = Instr::new_or_mask(&mask);
+        let x_mask = x_mask_for_addr(&mask, addr);
+        prop_assert_eq!(x_mask.len(), 36);
+
+        for (idx, ch) in x_mask.iter().enumerate() {
+            let bit = 35 - idx;
+            let and_bit = (and_mask >> bit) & 1;
+            let or_bit = (or_mask >> bit) & 1;
+            let addr_bit = if (addr >> bit) & 1 == 1 { '1' } else { '0' };
+            match (and_bit, or_bit) {
+                (1, 0) => prop_assert_eq!(*ch, 'X'),
+                (1, 1) => prop_assert_eq!(*ch, '1'),
+                (0, 0) => prop_assert_eq!(*ch, addr_bit),
+                _ => prop_assert!(false, "bit {} both cleared and set", bit),
+            }
+        }
+    }
+}
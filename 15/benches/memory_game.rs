@@ -0,0 +1,23 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc15::{read_starting_numbers, solve};
+
+fn bench_solve(c: &mut Criterion) {
+    let example: Vec<u32> = vec![0, 3, 6];
+    let input = read_starting_numbers("input").unwrap();
+
+    let mut group = c.benchmark_group("solve");
+    group.bench_function("example/2020", |b| b.iter(|| solve(&example, 2020).unwrap()));
+    group.bench_function("input/2020", |b| b.iter(|| solve(&input, 2020).unwrap()));
+    group.finish();
+
+    // Each run of 30 million turns takes seconds, so take the fewest samples criterion allows.
+    let mut group = c.benchmark_group("solve_30000000");
+    group.sample_size(10);
+    group.bench_function("example", |b| b.iter(|| solve(&example, 30000000).unwrap()));
+    group.bench_function("input", |b| b.iter(|| solve(&input, 30000000).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_solve);
+criterion_main!(benches);
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use aoc_common::{certificate_field, check_answer, json, parse_comma_numbers, parse_lines, read_parsed, Certificate, Certified, Error, InputSource, Iterations, Params, ParseError, Solution};
+
+pub fn read_starting_numbers<S: Into<InputSource>>(source: S) -> Result<Vec<u32>, Error> {
+    return read_parsed(source, parse_starting_numbers);
+}
+
+// One comma separated line of at least one number.
+pub fn parse_starting_numbers(contents: &str) -> Result<Vec<u32>, ParseError> {
+    let mut lines = parse_lines(contents, parse_comma_numbers)?;
+    if lines.len() != 1 {
+        let offset = contents.find('\n').map(|i| i + 1).unwrap_or(0);
+        return Err(ParseError::at(contents, offset, "a single line of starting numbers"));
+    }
+    return Ok(lines.remove(0));
+}
+
+pub fn solve(input: &Vec<u32>, n_turns: u32) -> Result<u32, Error> {
+    return solve_traced(input, n_turns, None);
+}
+
+/// Plays the game like `solve`, appending every number said to `spoken` if given.
+pub fn solve_traced(input: &Vec<u32>, n_turns: u32, mut spoken: Option<&mut Vec<u32>>) -> Result<u32, Error> {
+    if let Some(spoken) = spoken.as_mut() {
+        spoken.extend(input);
+    }
+    let mut mem: HashMap<u32, (u32, u32)> = HashMap::new();
+    for (idx, &num) in input.iter().enumerate() {
+        mem.insert(num, (idx as u32 + 1, 0));
+    }
+
+    let mut turn: u32 = input.len() as u32 + 1;
+    let mut prev_value: u32 = input[input.len() - 1];
+    let mut turns_for_prev: (u32, u32) = *mem.get(&prev_value).unwrap();
+    let turns_left = n_turns.saturating_sub(input.len() as u32) as u64;
+    let mut turns = Iterations::checking_every(1 << 16).reporting("turns", Some(turns_left));
+    while turn <= n_turns {
+        turns.tick()?;
+        // let turns_for_prev = mem.get_mut(&prev_value).unwrap();
+        let to_say = match turns_for_prev.1 {
+            0 => 0,
+            _ => {
+                turns_for_prev.0 - turns_for_prev.1
+            }
+        };
+
+        match mem.get_mut(&to_say) {
+            Some(turns_for_said) => {
+                turns_for_prev = (turn, turns_for_said.0);
+                mem.insert(to_say, turns_for_prev);
+            },
+            None => { 
+                turns_for_prev = (turn, 0);
+                mem.insert(to_say, turns_for_prev);
+            },
+        };
+
+        prev_value = to_say;
+        if let Some(spoken) = spoken.as_mut() {
+            spoken.push(to_say);
+        }
+        turn += 1;
+    }
+
+    return Ok(prev_value);
+}
+
+// The certificates only make sense once the game is past its starting numbers.
+fn turns_param(input: &Vec<u32>, params: &Params, default: u32) -> Result<u32, Error> {
+    let n_turns = params.get_or("turns", default)?;
+    if n_turns as usize <= input.len() {
+        return Err(Error::NoAnswer(format!("{} turns do not get past the {} starting numbers", n_turns, input.len())));
+    }
+    return Ok(n_turns);
+}
+
+/// Confirms that the certificate's `spoken` numbers start with the input and
+/// each follows the rules from those before, `answer` being the last.
+pub fn check_spoken(input: &Vec<u32>, n_turns: u32, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let spoken: Vec<u32> = certificate_field(certificate, "spoken")?;
+    if spoken.len() != n_turns as usize {
+        return Err(Error::Certificate(format!("{} numbers are said in {} turns", spoken.len(), n_turns)));
+    }
+    if !spoken.starts_with(input) {
+        return Err(Error::Certificate("the game does not start with the starting numbers".to_string()));
+    }
+    let mut last_said: HashMap<u32, usize> = HashMap::new();
+    for idx in 1..spoken.len() {
+        let prev = spoken[idx - 1];
+        let earlier = last_said.insert(prev, idx - 1);
+        let expected = earlier.map(|e| (idx - 1 - e) as u32).unwrap_or(0);
+        if idx >= input.len() && spoken[idx] != expected {
+            return Err(Error::Certificate(format!("turn {} should say {}, not {}", idx + 1, expected, spoken[idx])));
+        }
+    }
+    check_answer(spoken[spoken.len() - 1], answer)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Vec<u32>, Error> {
+        Ok(parse_starting_numbers(text)?)
+    }
+
+    const PARAMS: &'static [&'static str] = &["turns"];
+
+    const VERSION: u32 = 3;
+
+    fn part1(input: &Vec<u32>) -> Result<Certified<u32>, Error> {
+        Day15::part1_with(input, &Params::new())
+    }
+
+    fn part2(input: &Vec<u32>) -> Result<Certified<u32>, Error> {
+        Day15::part2_with(input, &Params::new())
+    }
+
+    fn part1_with(input: &Vec<u32>, params: &Params) -> Result<Certified<u32>, Error> {
+        let mut spoken = Vec::new();
+        let answer = solve_traced(input, turns_param(input, params, 2020)?, Some(&mut spoken))?;
+        Ok(Certified::new(answer, json!({ "spoken": spoken })))
+    }
+
+    fn part2_with(input: &Vec<u32>, params: &Params) -> Result<Certified<u32>, Error> {
+        // Every number of a 30 million turn game is too much to store, and anything
+        // less would take playing the game again to check.
+        Ok(Certified::unchecked(solve(input, turns_param(input, params, 30000000)?)?))
+    }
+
+    fn check(input: &Vec<u32>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        Day15::check_with(input, part, certificate, answer, &Params::new())
+    }
+
+    fn check_with(input: &Vec<u32>, part: u8, certificate: &Certificate, answer: &str, params: &Params) -> Result<(), Error> {
+        match part {
+            1 => check_spoken(input, params.get_or("turns", 2020)?, certificate, answer),
+            _ => Err(Error::Certificate("part 2 answers have no certificate".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_1() {
+        let input: Vec<u32> = vec![0, 3, 6];
+        let answer = solve(&input, 2020).unwrap();
+        assert_eq!(answer, 436);
+    }
+
+    #[test]
+    fn test_parse_starting_numbers() {
+        assert_eq!(parse_starting_numbers("0,3,6\n"), Ok(vec![0, 3, 6]));
+        let err = parse_starting_numbers("0, 3,6").err().unwrap();
+        assert_eq!((err.column, err.found), (3, Some(" ".to_string())));
+        let err = parse_starting_numbers("").err().unwrap();
+        assert_eq!(err.expected, "a single line of starting numbers");
+    }
+
+    #[test]
+    fn test_check_certificates() {
+        let input: Vec<u32> = vec![0, 3, 6];
+        let mut params = Params::new();
+        params.set("turns", "10");
+        let solved = Day15::part1_with(&input, &params).unwrap();
+        assert_eq!(solved.certificate, json!({ "spoken": [0, 3, 6, 0, 3, 3, 1, 0, 4, 0] }));
+        assert!(Day15::check_with(&input, 1, &solved.certificate, "0", &params).is_ok());
+        let err = Day15::check_with(&input, 1, &json!({ "spoken": [0, 3, 6, 0, 3, 3, 1, 0, 4, 1] }), "1", &params).unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: turn 10 should say 0, not 1");
+
+        let solved = Day15::part2_with(&input, &params).unwrap();
+        assert_eq!((solved.answer, solved.certificate), (0, Certificate::Null));
+        assert!(Day15::check_with(&input, 2, &json!({ "said_on": [9, 9] }), "0", &params).is_err());
+
+        params.set("turns", "3");
+        let err = Day15::part1_with(&input, &params).unwrap_err();
+        assert_eq!(err.to_string(), "no answer: 3 turns do not get past the 3 starting numbers");
+    }
+
+    #[test]
+    fn test_example_2_1() {
+        let input: Vec<u32> = vec![0, 3, 6];
+        let answer = solve(&input, 30000000).unwrap();
+        assert_eq!(answer, 175594);
+    }
+}
@@ -0,0 +1,116 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const CATEGORY_NAMES: [&str; 20] = [
+    "departure location", "departure station", "departure platform", "departure track",
+    "departure date", "departure time", "arrival location", "arrival station",
+    "arrival platform", "arrival track", "class", "duration", "price", "route", "row",
+    "seat", "train", "type", "wagon", "zone",
+];
+
+// Categories start allowing values somewhere from MIN_START to MIN_VALUE, but
+// ticket values are at least MIN_VALUE. No category allows values in the gap.
+const MIN_START: u32 = 25;
+const MIN_VALUE: u32 = 50;
+const GAP: (u32, u32) = (180, 199);
+
+fn valid_value(rng: &mut StdRng, max: u32) -> u32 {
+    loop {
+        let x = rng.gen_range(MIN_VALUE..=max);
+        if x < GAP.0 || GAP.1 < x {
+            return x;
+        }
+    }
+}
+
+fn invalid_value(rng: &mut StdRng, max: u32) -> u32 {
+    match rng.gen_range(0..3) {
+        0 => rng.gen_range(0..MIN_START),
+        1 => rng.gen_range(GAP.0..=GAP.1),
+        _ => rng.gen_range(max + 1..=max + 100),
+    }
+}
+
+/// Notes on 20 categories, my ticket and `size` nearby tickets, at least 1.
+/// About a fifth of the nearby tickets have an invalid value.
+///
+/// Categories are ranked so that each allows values up to a higher maximum
+/// than the last. One valid ticket has, in each category's position, a value
+/// above the maximum of every lower ranked category. That leaves the position
+/// of the top ranked category with one candidate, and eliminating it in turn
+/// settles every other position.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = CATEGORY_NAMES.len();
+
+    // ranks[i] is the rank of CATEGORY_NAMES[i], and positions[rank] is where its value goes on tickets.
+    let mut ranks: Vec<usize> = (0..n).collect();
+    ranks.shuffle(&mut rng);
+    let mut positions: Vec<usize> = (0..n).collect();
+    positions.shuffle(&mut rng);
+
+    let mut maxima: Vec<u32> = Vec::new();
+    let mut max = GAP.1 + 40;
+    for _ in 0..n {
+        max = max + rng.gen_range(10..=40);
+        maxima.push(max);
+    }
+
+    let mut text = String::new();
+    for (idx, name) in CATEGORY_NAMES.iter().enumerate() {
+        let lo = rng.gen_range(MIN_START..=MIN_VALUE);
+        text.push_str(&format!("{}: {}-{} or {}-{}\n", name, lo, GAP.0 - 1, GAP.1 + 1, maxima[ranks[idx]]));
+    }
+
+    let random_ticket = |rng: &mut StdRng| -> Vec<u32> {
+        let mut ticket = vec![0; n];
+        for rank in 0..n {
+            ticket[positions[rank]] = valid_value(rng, maxima[rank]);
+        }
+        ticket
+    };
+
+    let mut nearby: Vec<Vec<u32>> = Vec::new();
+    let mut witness = random_ticket(&mut rng);
+    for rank in 1..n {
+        witness[positions[rank]] = rng.gen_range(maxima[rank - 1] + 1..=maxima[rank]);
+    }
+    nearby.push(witness);
+    while nearby.len() < size {
+        let mut ticket = random_ticket(&mut rng);
+        if rng.gen_bool(0.2) {
+            let pos = rng.gen_range(0..n);
+            ticket[pos] = invalid_value(&mut rng, maxima[n - 1]);
+        }
+        nearby.push(ticket);
+    }
+    nearby.shuffle(&mut rng);
+
+    let join = |ticket: &Vec<u32>| ticket.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+    text.push_str(&format!("\nyour ticket:\n{}\n\nnearby tickets:\n", join(&random_ticket(&mut rng))));
+    for ticket in &nearby {
+        text.push_str(&join(ticket));
+        text.push('\n');
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calc_puzzle_error_rate, parse_puzzle, solve_for_field_positions};
+
+    #[test]
+    fn test_generate() {
+        let puz = parse_puzzle(&generate(100, 16)).unwrap();
+        assert_eq!(puz.tickets.len(), 101);
+        assert!(calc_puzzle_error_rate(&puz) > 0);
+
+        let mut fields_by_pos = solve_for_field_positions(&puz).unwrap();
+        fields_by_pos.sort();
+        let mut names: Vec<String> = CATEGORY_NAMES.iter().map(|x| x.to_string()).collect();
+        names.sort();
+        assert_eq!(fields_by_pos, names);
+    }
+}
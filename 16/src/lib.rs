@@ -0,0 +1,419 @@
+pub mod generator;
+
+use log::{debug, trace};
+
+use aoc_common::combinator::{cut, end_of_line, expect, literal, pair, parse_line, range, separated_list1, take_while1, terminated, PResult};
+use aoc_common::{certificate_field, check_answer, checked_mul, json, parse_comma_numbers, read_parsed, split_sections, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+#[cfg(not(feature = "bigint"))]
+pub type Product = u64;
+#[cfg(feature = "bigint")]
+pub type Product = aoc_common::BigUint;
+
+// Part 1: error_rate = 20048
+
+pub struct PuzzleInput {
+    pub categories: Vec<(String, Vec<(u32, u32)>)>,
+    pub tickets: Vec<Vec<u32>>,
+}
+
+pub fn category_name(input: &str) -> PResult<'_, &str> {
+    let name = take_while1(|ch: char| ch.is_alphanumeric() || ch == ' ' || ch == '_');
+    return terminated(expect("a category name followed by `: `", name), literal(": "))(input);
+}
+
+pub fn category_ranges(input: &str) -> PResult<'_, Vec<(u32, u32)>> {
+    let ranges = separated_list1(literal(" or "), cut(expect("a range like `1-3`", range())));
+    return terminated(ranges, expect("` or ` or the end of the line", end_of_line()))(input);
+}
+
+pub fn build_category(ln: &str) -> Result<(String, Vec<(u32, u32)>), ParseError> {
+    let (name, ranges) = parse_line(ln, pair(category_name, category_ranges))?;
+    return Ok((name.to_string(), ranges));
+}
+
+pub fn build_numeric_series(ln: &str) -> Result<Vec<u32>, ParseError> {
+    return parse_comma_numbers(ln);
+}
+
+pub fn read_puzzle<S: Into<InputSource>>(source: S) -> Result<PuzzleInput, Error> {
+    return read_parsed(source, parse_puzzle);
+}
+
+// Three sections separated by blank lines: the categories, `your ticket:` and
+// `nearby tickets:`. My ticket is kept as the first of the tickets.
+pub fn parse_puzzle(contents: &str) -> Result<PuzzleInput, ParseError> {
+    let headers = ["your ticket:", "nearby tickets:"];
+    let sections = split_sections(contents);
+    let categories: Vec<(String, Vec<(u32, u32)>)> = match sections.first() {
+        Some(section) => section.parse_lines(build_category)?,
+        None => Vec::new(),
+    };
+    let mut numeric_series: Vec<Vec<u32>> = Vec::new();
+
+    // Any sections after `nearby tickets:` hold more nearby tickets.
+    for (idx, section) in sections.iter().enumerate().skip(1) {
+        let mut lines = section.lines();
+        if let Some(header) = headers.get(idx - 1) {
+            let (line_no, ln) = lines.next().unwrap();
+            if ln != *header {
+                return Err(ParseError::at_start(ln, &format!("`{}`", header)).on_line(line_no));
+            }
+        }
+        for (line_no, ln) in lines {
+            if idx == 1 && numeric_series.len() == 1 {
+                return Err(ParseError::at_start(ln, "a blank line after my ticket").on_line(line_no));
+            }
+            let series = build_numeric_series(ln).map_err(|err| err.on_line(line_no))?;
+            if series.len() != categories.len() {
+                let expected = format!("{} values, one per category", categories.len());
+                return Err(ParseError::at_start(ln, &expected).on_line(line_no));
+            }
+            numeric_series.push(series);
+        }
+    }
+
+    if numeric_series.is_empty() {
+        return Err(ParseError::at(contents, contents.len(), "`your ticket:` and its values"));
+    }
+
+    Ok(PuzzleInput{
+        categories: categories,
+        tickets: numeric_series,
+    })
+}
+
+pub fn value_is_valid(x: u32, categories: &Vec<(String, Vec<(u32, u32)>)>) -> bool {
+    for (_, cat_ranges) in categories {
+        for range in cat_ranges {
+            if range.0 <= x && x <= range.1 {
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+pub fn ticket_is_valid(ticket: &Vec<u32>, categories: &Vec<(String, Vec<(u32, u32)>)>) -> bool {
+    for x in ticket.iter().copied() {
+        if !value_is_valid(x, categories) {
+            return false;
+        }
+    }
+    return true;
+}
+
+// TODO: A ticket_is_valid function needs to be extracted from this and then used to filter out
+// invalid tickets for part 2.
+pub fn calc_puzzle_error_rate(puz: &PuzzleInput) -> u32 {
+    return error_rate_of(puz, &invalid_values(puz));
+}
+
+/// The ticket and position of each value that is in no category's ranges.
+pub fn invalid_values(puz: &PuzzleInput) -> Vec<(usize, usize)> {
+    let mut invalid = Vec::new();
+    for (ticket_idx, ticket) in puz.tickets.iter().enumerate() {
+        for (pos, x) in ticket.iter().copied().enumerate() {
+            if value_is_valid(x, &puz.categories) {
+                continue;
+            }
+            debug!("ticket value {} is invalid", x);
+            invalid.push((ticket_idx, pos));
+        }
+    }
+    return invalid;
+}
+
+fn error_rate_of(puz: &PuzzleInput, invalid: &Vec<(usize, usize)>) -> u32 {
+    let mut accum: u32 = 0;
+    for (ticket_idx, pos) in invalid {
+        accum += puz.tickets[*ticket_idx][*pos];
+    }
+    return accum;
+}
+
+// The values listed as `invalid` must be exactly those in none of the categories'
+// ranges, and must add up to `answer`.
+pub fn check_invalid_values(puz: &PuzzleInput, invalid: &Vec<(usize, usize)>, answer: &str) -> Result<(), Error> {
+    let mut listed: Vec<(usize, usize)> = invalid.clone();
+    listed.sort();
+    listed.dedup();
+    if listed.len() != invalid.len() {
+        return Err(Error::Certificate("a value is listed twice".to_string()));
+    }
+    let mut total: u64 = 0;
+    for (ticket_idx, ticket) in puz.tickets.iter().enumerate() {
+        for (pos, x) in ticket.iter().enumerate() {
+            let fits = puz.categories.iter().flat_map(|(_, ranges)| ranges).any(|(start, end)| start <= x && x <= end);
+            let is_listed = listed.binary_search(&(ticket_idx, pos)).is_ok();
+            if fits == is_listed {
+                let verdict = if fits { "is valid" } else { "is not listed" };
+                return Err(Error::Certificate(format!("{} at position {} of ticket {} {}", x, pos, ticket_idx, verdict)));
+            }
+            if is_listed {
+                total = total + *x as u64;
+            }
+        }
+    }
+    if let Some((ticket_idx, pos)) = listed.iter().find(|(t, p)| puz.tickets.get(*t).and_then(|ticket| ticket.get(*p)).is_none()) {
+        return Err(Error::Certificate(format!("ticket {} has no position {}", ticket_idx, pos)));
+    }
+    check_answer(total, answer)
+}
+
+pub fn solve_for_field_positions(puz: &PuzzleInput) -> Result<Vec<String>, Error> {
+    let cat_names: Vec<String> = puz.categories.iter().map(|(cat_name, _)| cat_name.clone()).collect();
+    let mut possible_fields_by_pos: Vec<Vec<String>> = (0..puz.categories.len()).map(|_| {
+        cat_names.clone()
+    }).collect();
+
+    let valid_tickets: Vec<&Vec<u32>> = puz.tickets.iter().filter(|t| {
+        ticket_is_valid(t, &puz.categories)
+    }).collect();
+
+    for ticket in &valid_tickets {
+        for (idx, x) in ticket.iter().enumerate() {
+            'cat_bump: for (cat_name, cat_ranges) in &puz.categories {
+                for (range_start, range_end) in cat_ranges {
+                    if range_start <= x && x <= range_end {
+                        continue 'cat_bump;
+                    }
+                }
+                debug!("Value {} in position {} not valid for any range of category {}",
+                    x, idx, cat_name);
+                let fields_for_pos = possible_fields_by_pos[idx].clone();
+                possible_fields_by_pos[idx] = fields_for_pos.into_iter().filter(|cn| {
+                    cn != cat_name
+                }).collect();
+                for (idx, cat_names) in possible_fields_by_pos.iter().enumerate() {
+                    trace!("{}: {:?}", idx, cat_names);
+                }
+            }
+        }
+    }
+
+    let mut updates = 1;
+    while updates > 0 {
+        updates = 0;
+
+        let unique_fields: Vec<(usize, String)> = possible_fields_by_pos.iter().enumerate()
+            .filter(|(_, cat_names)| cat_names.len() == 1)
+            .map(|(idx, cat_names)| (idx, cat_names[0].clone()))
+            .collect();
+
+        for (unique_idx, unique_cat_name) in &unique_fields {
+            for (idx, fields_for_pos) in possible_fields_by_pos.iter_mut().enumerate() {
+                if idx != *unique_idx {
+                    let pruned_possibilities: Vec<String> = fields_for_pos.clone().into_iter().filter(|cat_name| {
+                        cat_name != unique_cat_name
+                    }).collect();
+                    if pruned_possibilities.len() < fields_for_pos.len() {
+                        debug!("Removing category {} from set for position {} because it is unique to position {}", unique_cat_name, idx, unique_idx);
+                        *fields_for_pos = pruned_possibilities;
+                        updates += 1;
+                        trace!("updates = {}", updates);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    for (idx, field_list) in possible_fields_by_pos.iter().enumerate() {
+        if field_list.len() == 0 {
+            return Err(Error::NoAnswer(format!("position {} has no possible categories", idx)));
+        } else if field_list.len() > 1 {
+            return Err(Error::NoAnswer(format!("position {} could be any of {} categories", idx, field_list.len())));
+        }
+        result.push(field_list[0].clone());
+    }
+
+    return Ok(result);
+}
+
+pub fn departure_product(puz: &PuzzleInput, fields_by_pos: &Vec<String>) -> Result<Product, Error> {
+    let mut accum = Product::from(1_u32);
+    for (idx, cat_name) in fields_by_pos.iter().enumerate() {
+        if cat_name.starts_with("departure") {
+            let value = puz.tickets[0][idx];
+            debug!("my ticket: {} = {}", cat_name, value);
+            accum = checked_mul(&accum, &Product::from(value), "the product of the departure fields")?;
+        }
+    }
+    return Ok(accum);
+}
+
+// Each category must be at exactly one position, every valid ticket's value there must
+// be in the category's ranges, and my departure fields must multiply to `answer`.
+pub fn check_field_positions(puz: &PuzzleInput, fields_by_pos: &Vec<String>, answer: &str) -> Result<(), Error> {
+    if fields_by_pos.len() != puz.categories.len() {
+        return Err(Error::Certificate(format!("{} positions for {} categories", fields_by_pos.len(), puz.categories.len())));
+    }
+    let mut ranges_by_pos: Vec<&Vec<(u32, u32)>> = Vec::new();
+    for (idx, name) in fields_by_pos.iter().enumerate() {
+        if fields_by_pos[..idx].contains(name) {
+            return Err(Error::Certificate(format!("category {} is at more than one position", name)));
+        }
+        match puz.categories.iter().find(|(cat_name, _)| cat_name == name) {
+            Some((_, ranges)) => ranges_by_pos.push(ranges),
+            None => return Err(Error::Certificate(format!("there is no category {}", name))),
+        }
+    }
+
+    for ticket in puz.tickets.iter().filter(|t| ticket_is_valid(t, &puz.categories)) {
+        for (idx, x) in ticket.iter().enumerate() {
+            if !ranges_by_pos[idx].iter().any(|(start, end)| start <= x && x <= end) {
+                return Err(Error::Certificate(format!("{} at position {} is not a valid {}", x, idx, fields_by_pos[idx])));
+            }
+        }
+    }
+
+    let product = departure_product(puz, fields_by_pos)?;
+    if product.to_string() != answer {
+        return Err(Error::Certificate(format!("the departure fields multiply to {}", product)));
+    }
+    return Ok(());
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = PuzzleInput;
+    type Answer1 = u32;
+    type Answer2 = Product;
+
+    const VERSION: u32 = 3;
+
+    fn parse(text: &str) -> Result<PuzzleInput, Error> {
+        Ok(parse_puzzle(text)?)
+    }
+
+    fn part1(puz: &PuzzleInput) -> Result<Certified<u32>, Error> {
+        let invalid = invalid_values(puz);
+        Ok(Certified::new(error_rate_of(puz, &invalid), json!({ "invalid": invalid })))
+    }
+
+    fn part2(puz: &PuzzleInput) -> Result<Certified<Product>, Error> {
+        let fields_by_pos = solve_for_field_positions(puz)?;
+        let product = departure_product(puz, &fields_by_pos)?;
+        Ok(Certified::new(product, json!({ "fields": fields_by_pos })))
+    }
+
+    fn check(puz: &PuzzleInput, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_invalid_values(puz, &certificate_field(certificate, "invalid")?, answer),
+            _ => check_field_positions(puz, &certificate_field(certificate, "fields")?, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example1() {
+        let puz = read_puzzle("example1").unwrap();
+        for (nm, _ranges) in &puz.categories {
+            println!("name = {}", nm);
+        }
+        assert_eq!(puz.categories.len(), 3);
+        assert_eq!(puz.tickets.len(), 5);
+
+        let error_rate = calc_puzzle_error_rate(&puz);
+        assert_eq!(error_rate, 71);
+    }
+
+    #[test]
+    fn test_example1_build_category() {
+        let ln = "class: 1-3 or 5-7";
+        let (nm, ranges) = build_category(ln).unwrap();
+        assert_eq!(nm, "class");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0], (1, 3));
+        assert_eq!(ranges[1], (5, 7));
+    }
+
+    #[test]
+    fn test_example1_build_numeric_series1() {
+        let series = build_numeric_series("7,1,14").unwrap();
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0], 7_u32);
+        assert_eq!(series[1], 1_u32);
+        assert_eq!(series[2], 14_u32);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = build_numeric_series("7,1,14 ").err().unwrap();
+        assert_eq!((err.column, err.found), (7, Some(" ".to_string())));
+        let err = build_category("class: 1-3 5-7").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (11, "` or ` or the end of the line"));
+        let err = build_category("class: 1-3 or 5-x").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (17, "a digit"));
+        let err = build_category("class").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (6, "`: `"));
+        let err = parse_puzzle("class: 1-3 or 5-7\n\nyour ticket:\n7,1\n").err().unwrap();
+        assert_eq!((err.line, err.column), (4, 1));
+    }
+
+    #[test]
+    fn test_ambiguous_positions() {
+        let puz = Day16::parse("a: 1-5 or 7-9\nb: 1-5 or 7-9\n\nyour ticket:\n1,2\n\nnearby tickets:\n3,4\n").unwrap();
+        let err = Day16::part2(&puz).err().unwrap();
+        assert_eq!(err.to_string(), "no answer: position 0 could be any of 2 categories");
+
+        let puz = Day16::parse("a: 1-2 or 3-5\nb: 7-8 or 9-10\n\nyour ticket:\n1,7\n\nnearby tickets:\n3,8\n8,3\n").unwrap();
+        let err = Day16::part2(&puz).err().unwrap();
+        assert_eq!(err.to_string(), "no answer: position 0 has no possible categories");
+    }
+
+    #[test]
+    fn test_example2() {
+        let puz = read_puzzle("example2").unwrap();
+        let solution = solve_for_field_positions(&puz).unwrap();
+        assert_eq!(solution[0], "row".to_string());
+        assert_eq!(solution[1], "class".to_string());
+        assert_eq!(solution[2], "seat".to_string());
+    }
+
+    #[test]
+    fn test_check_invalid_values() {
+        let puz = read_puzzle("example1").unwrap();
+        let certificate = Day16::part1(&puz).unwrap().certificate;
+        assert_eq!(certificate, json!({ "invalid": [[2, 1], [3, 0], [4, 2]] }));
+        assert!(Day16::check(&puz, 1, &certificate, "71").is_ok());
+
+        let err = Day16::check(&puz, 1, &json!({ "invalid": [[2, 1], [3, 0]] }), "59").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 12 at position 2 of ticket 4 is not listed");
+        let err = Day16::check(&puz, 1, &json!({ "invalid": [[1, 0], [2, 1], [3, 0], [4, 2]] }), "78").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 7 at position 0 of ticket 1 is valid");
+        let err = Day16::check(&puz, 1, &json!({ "invalid": [[2, 1], [3, 0], [4, 2], [9, 9]] }), "71").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: ticket 9 has no position 9");
+    }
+
+    #[test]
+    fn test_check_field_positions() {
+        let puz = read_puzzle("example2").unwrap();
+        let certificate = Day16::part2(&puz).unwrap().certificate;
+        assert_eq!(certificate, json!({ "fields": ["row", "class", "seat"] }));
+        assert!(Day16::check(&puz, 2, &certificate, "1").is_ok());
+
+        let err = Day16::check(&puz, 2, &json!({ "fields": ["class", "row", "seat"] }), "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 3 at position 0 is not a valid class");
+        let err = Day16::check(&puz, 2, &json!({ "fields": ["row", "row", "seat"] }), "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: category row is at more than one position");
+    }
+
+    #[test]
+    fn test_departure_product_overflow() {
+        let puz = PuzzleInput{ categories: vec![], tickets: vec![vec![u32::MAX, 7, u32::MAX, u32::MAX]] };
+        let fields_by_pos: Vec<String> = vec!["departure a", "row", "departure b", "departure c"]
+            .into_iter().map(|x| x.to_string()).collect();
+        let product = departure_product(&puz, &fields_by_pos);
+        #[cfg(not(feature = "bigint"))]
+        assert!(matches!(product, Err(Error::Overflow(_))));
+        #[cfg(feature = "bigint")]
+        assert_eq!(product.unwrap(), Product::from(u32::MAX).pow(3));
+    }
+}
@@ -0,0 +1,19 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc17::{read_plane, run_cycles, step_active_coords};
+
+fn bench_step_active_coords(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step_active_coords");
+    group.sample_size(10);
+    for filename in ["example1", "input"].iter() {
+        let initial = read_plane(*filename).unwrap();
+        // Step from the fifth generation, where the bounding cube is close to its largest.
+        let active_coords = run_cycles(&initial, 4, 5).unwrap();
+        group.bench_function(format!("{}/4d", filename), |b| b.iter(|| step_active_coords(&active_coords)));
+        group.bench_function(format!("{}/3d_6_cycles", filename), |b| b.iter(|| run_cycles(&initial, 3, 6).unwrap()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_step_active_coords);
+criterion_main!(benches);
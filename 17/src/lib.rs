@@ -0,0 +1,438 @@
+pub mod simulation;
+
+use std::cmp::Ord;
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_common::{certificate_field, check_answer, count_round, json, read_parsed, Certificate, Certified, Iterations, Error, Grid, InputSource, ParseError, Solution, Tile};
+
+// Part 1: 319 initial active coords
+// Part 2: 2324 initial active coords
+
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Coord {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub w: i32,
+}
+
+impl Coord {
+    pub fn new_2d(x: i32, y: i32) -> Coord {
+        Coord{x: x, y: y, z: 0, w: 0}
+    }
+
+    pub fn new_3d(x: i32, y: i32, z: i32) -> Coord {
+        Coord{x: x, y: y, z: z, w: 0}
+    }
+
+    pub fn new_4d(x: i32, y: i32, z: i32, w: i32) -> Coord {
+        Coord{x: x, y: y, z: z, w: w}
+    }
+
+    pub fn plus(self: &Coord, other: &Coord) -> Coord {
+        Coord{
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w,
+        }
+    }
+}
+
+pub fn read_plane<S: Into<InputSource>>(source: S) -> Result<BTreeSet<Coord>, Error> {
+    return read_parsed(source, parse_plane);
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Cube {
+    Inactive,
+    Active,
+}
+
+impl Tile for Cube {
+    const EXPECTED: &'static str = "`.` or `#`";
+
+    fn from_char(ch: char) -> Option<Cube> {
+        match ch {
+            '.' => Some(Cube::Inactive),
+            '#' => Some(Cube::Active),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Cube::Inactive => '.',
+            Cube::Active => '#',
+        }
+    }
+}
+
+pub fn parse_plane(contents: &str) -> Result<BTreeSet<Coord>, ParseError> {
+    let plane: Grid<Cube> = Grid::parse(contents)?;
+    let active_coords: BTreeSet<Coord> = plane.iter()
+        .filter(|(_, _, cube)| **cube == Cube::Active)
+        .map(|(x, y, _)| Coord::new_2d(x as i32, y as i32))
+        .collect();
+
+    return Ok(active_coords);
+}
+
+pub fn bounding_cube(coords: &BTreeSet<Coord>) -> (Coord, Coord) {
+    let mut x_min = i32::MAX;
+    let mut x_max = i32::MIN;
+    let mut y_min = i32::MAX;
+    let mut y_max = i32::MIN;
+    let mut z_min = i32::MAX;
+    let mut z_max = i32::MIN;
+    let mut w_min = i32::MAX;
+    let mut w_max = i32::MIN;
+
+    if coords.len() == 0 {
+        return (Coord::new_4d(0, 0, 0, 0), Coord::new_4d(0, 0, 0, 0));
+    }
+
+    for c in coords {
+        x_min = std::cmp::min(x_min, c.x);
+        y_min = std::cmp::min(y_min, c.y);
+        z_min = std::cmp::min(z_min, c.z);
+        w_min = std::cmp::min(w_min, c.w);
+
+        x_max = std::cmp::max(x_max, c.x);
+        y_max = std::cmp::max(y_max, c.y);
+        z_max = std::cmp::max(z_max, c.z);
+        w_max = std::cmp::max(w_max, c.w);
+    }
+
+    return (
+        Coord::new_4d(x_min, y_min, z_min, w_min),
+        Coord::new_4d(x_max, y_max, z_max, w_max)
+    );
+}
+
+pub struct NeighborCoords {
+    x_off_min: i32,
+    x_off_max: i32,
+    y_off_min: i32,
+    y_off_max: i32,
+    z_off_min: i32,
+    z_off_max: i32,
+    // w is the outermost axis so it never wraps back to its minimum.
+    #[allow(dead_code)]
+    w_off_min: i32,
+    w_off_max: i32,
+    x_curr: i32,
+    y_curr: i32,
+    z_curr: i32,
+    w_curr: i32,
+}
+
+impl NeighborCoords {
+    pub fn new_abs_3d(abs_range: i32) -> NeighborCoords {
+        let neg = -abs_range.abs();
+        let pos = abs_range.abs();
+        NeighborCoords{
+            x_off_min: neg,
+            x_off_max: pos,
+            y_off_min: neg,
+            y_off_max: pos,
+            z_off_min: neg,
+            z_off_max: pos,
+            w_off_min: 0,
+            w_off_max: 0,
+            x_curr: neg,
+            y_curr: neg,
+            z_curr: neg,
+            w_curr: 0,
+        }
+    }
+
+    pub fn new_abs_4d(abs_range: i32) -> NeighborCoords {
+        let neg = -abs_range.abs();
+        let pos = abs_range.abs();
+        NeighborCoords{
+            x_off_min: neg,
+            x_off_max: pos,
+            y_off_min: neg,
+            y_off_max: pos,
+            z_off_min: neg,
+            z_off_max: pos,
+            w_off_min: neg,
+            w_off_max: pos,
+            x_curr: neg,
+            y_curr: neg,
+            z_curr: neg,
+            w_curr: neg,
+        }
+    }
+}
+
+impl Iterator for NeighborCoords {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Coord> {
+        if (self.x_curr > self.x_off_max) && (self.y_curr > self.y_off_max) && (self.z_curr > self.z_off_max) && (self.w_curr > self.w_off_max) {
+            return None;
+        }
+
+        if (self.x_curr == 0) && (self.y_curr == 0) && (self.z_curr == 0) && (self.w_curr == 0) {
+            self.x_curr += 1;
+        }
+
+        let result_coord: Coord = Coord::new_4d(self.x_curr, self.y_curr, self.z_curr, self.w_curr);
+
+        if self.x_curr == self.x_off_max {
+            if self.y_curr == self.y_off_max {
+                if self.z_curr == self.z_off_max {
+                    if self.w_curr == self.w_off_max {
+                        // Invalidate so we'll return None on the subsequent call.
+                        self.z_curr += 1;
+                        self.x_curr += 1;
+                        self.y_curr += 1;
+                        self.w_curr += 1;
+                    } else {
+                        self.w_curr += 1;
+                        self.z_curr = self.z_off_min;
+                        self.y_curr = self.y_off_min;
+                        self.x_curr = self.x_off_min;
+                    }
+                } else {
+                    self.z_curr += 1;
+                    self.y_curr = self.y_off_min;
+                    self.x_curr = self.x_off_min;
+                }
+            } else {
+                self.y_curr += 1;
+                self.x_curr = self.x_off_min;
+            }
+        } else {
+            self.x_curr += 1;
+        }
+ 
+        return Some(result_coord);
+    }
+}
+
+pub fn step_active_coords(active_coords: &BTreeSet<Coord>) -> BTreeSet<Coord> {
+    return step_active_coords_in(active_coords, 4);
+}
+
+// Runs one cycle in either 3 or 4 dimensions. In 3 dimensions every coord keeps w = 0.
+pub fn step_active_coords_in(active_coords: &BTreeSet<Coord>, dimensions: u32) -> BTreeSet<Coord> {
+    let mut new_coords: BTreeSet<Coord> = BTreeSet::new();
+    let mut evaluated: u64 = 0;
+    let (min_coord, max_coord) = bounding_cube(active_coords);
+    let (w_min, w_max) = match dimensions {
+        3 => (0, 0),
+        4 => (min_coord.w - 1, max_coord.w + 1),
+        _ => panic!("Only 3 and 4 dimensions are supported, not {}.", dimensions),
+    };
+
+    for w in w_min..(w_max + 1) {
+        for z in min_coord.z-1..(max_coord.z + 2) {
+            for y in min_coord.y-1..(max_coord.y + 2) {
+                for x in min_coord.x-1..(max_coord.x + 2) {
+                    let curr_coord = Coord::new_4d(x, y, z, w);
+                    evaluated = evaluated + 1;
+                    let neighbors = match dimensions {
+                        3 => NeighborCoords::new_abs_3d(1),
+                        _ => NeighborCoords::new_abs_4d(1),
+                    };
+                    let active_neighbor_count: u32 = neighbors.map(|nc| {
+                        match active_coords.contains(&nc.plus(&curr_coord)) {
+                            true => 1,
+                            false => 0,
+                        }
+                    }).sum();
+
+                    let curr_is_active = active_coords.contains(&curr_coord);
+                    if (active_neighbor_count == 3) || (curr_is_active && (active_neighbor_count == 2)) {
+                        new_coords.insert(curr_coord);
+                    }
+                }
+            }
+        }
+    }
+
+    count_round("cells_evaluated", evaluated);
+    return new_coords;
+}
+
+// The space grows every cycle, so later cycles take longest and each is a chance to stop.
+// A cycle counts once it has finished, so progress and its ETA only cover finished work.
+pub fn run_cycles(initial: &BTreeSet<Coord>, dimensions: u32, cycles: u32) -> Result<BTreeSet<Coord>, Error> {
+    return run_cycles_traced(initial, dimensions, cycles, &mut Vec::new());
+}
+
+/// Runs the cycles like `run_cycles`, recording the active coords after each.
+pub fn run_cycles_traced(initial: &BTreeSet<Coord>, dimensions: u32, cycles: u32, history: &mut Vec<BTreeSet<Coord>>) -> Result<BTreeSet<Coord>, Error> {
+    let mut iterations = Iterations::new().reporting("cycles", Some(cycles as u64));
+    let mut active_coords = initial.clone();
+    for _ in 0..cycles {
+        active_coords = step_active_coords_in(&active_coords, dimensions);
+        history.push(active_coords.clone());
+        iterations.tick()?;
+    }
+    return Ok(active_coords);
+}
+
+fn certify_cycles(initial: &BTreeSet<Coord>, dimensions: u32) -> Result<Certified<usize>, Error> {
+    let mut history = Vec::new();
+    let active_coords = run_cycles_traced(initial, dimensions, 6, &mut history)?;
+    let cycles: Vec<Vec<[i32; 4]>> = history.iter()
+        .map(|coords| coords.iter().map(|c| [c.x, c.y, c.z, c.w]).collect())
+        .collect();
+    return Ok(Certified::new(active_coords.len(), json!({ "cycles": cycles })));
+}
+
+// Counts each active cube's neighbours by spreading from it, rather than looking
+// around every coord of a bounding box as `step_active_coords_in` does.
+fn next_cycle(active: &BTreeSet<[i32; 4]>, dimensions: u32) -> BTreeSet<[i32; 4]> {
+    let span = |axis: u32| if axis < dimensions { -1..=1 } else { 0..=0 };
+    let mut offsets: Vec<[i32; 4]> = Vec::new();
+    for dw in span(3) {
+        for dz in span(2) {
+            for dy in span(1) {
+                for dx in span(0) {
+                    if [dx, dy, dz, dw] != [0, 0, 0, 0] {
+                        offsets.push([dx, dy, dz, dw]);
+                    }
+                }
+            }
+        }
+    }
+    let mut neighbors: HashMap<[i32; 4], u32> = HashMap::new();
+    for c in active {
+        for o in &offsets {
+            *neighbors.entry([c[0] + o[0], c[1] + o[1], c[2] + o[2], c[3] + o[3]]).or_insert(0) += 1;
+        }
+    }
+    return neighbors.into_iter()
+        .filter(|(c, n)| *n == 3 || (*n == 2 && active.contains(c)))
+        .map(|(c, _)| c)
+        .collect();
+}
+
+/// Confirms that each of the certificate's `cycles` follows from the one before,
+/// starting from the input, and that the last has `answer` active cubes.
+pub fn check_cycles(initial: &BTreeSet<Coord>, dimensions: u32, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let cycles: Vec<Vec<[i32; 4]>> = certificate_field(certificate, "cycles")?;
+    if cycles.len() != 6 {
+        return Err(Error::Certificate(format!("{} cycles, not 6", cycles.len())));
+    }
+    let mut active: BTreeSet<[i32; 4]> = initial.iter().map(|c| [c.x, c.y, c.z, c.w]).collect();
+    for (idx, listed) in cycles.iter().enumerate() {
+        active = next_cycle(&active, dimensions);
+        let listed: BTreeSet<[i32; 4]> = listed.iter().cloned().collect();
+        if let Some(c) = active.symmetric_difference(&listed).next() {
+            let verdict = if active.contains(c) { "active" } else { "inactive" };
+            return Err(Error::Certificate(format!("{:?} is {} after cycle {}", c, verdict, idx + 1)));
+        }
+    }
+    check_answer(active.len(), answer)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = BTreeSet<Coord>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<BTreeSet<Coord>, Error> {
+        Ok(parse_plane(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(initial: &BTreeSet<Coord>) -> Result<Certified<usize>, Error> {
+        certify_cycles(initial, 3)
+    }
+
+    fn part2(initial: &BTreeSet<Coord>) -> Result<Certified<usize>, Error> {
+        certify_cycles(initial, 4)
+    }
+
+    fn check(initial: &BTreeSet<Coord>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_cycles(initial, if part == 1 { 3 } else { 4 }, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_1() {
+        let mut active_coords = read_plane("example1").unwrap();
+        assert_eq!(active_coords.contains(&Coord::new_3d(0, 0, 0)), false);
+        assert_eq!(active_coords.contains(&Coord::new_3d(1, 0, 0)), true);
+        assert_eq!(active_coords.contains(&Coord::new_3d(2, 0, 0)), false);
+
+        assert_eq!(active_coords.contains(&Coord::new_3d(0, 1, 0)), false);
+        assert_eq!(active_coords.contains(&Coord::new_3d(1, 1, 0)), false);
+        assert_eq!(active_coords.contains(&Coord::new_3d(2, 1, 0)), true);
+
+        assert_eq!(active_coords.contains(&Coord::new_3d(0, 2, 0)), true);
+        assert_eq!(active_coords.contains(&Coord::new_3d(1, 2, 0)), true);
+        assert_eq!(active_coords.contains(&Coord::new_3d(2, 2, 0)), true);
+
+        for _ in 0..6 {
+            active_coords = step_active_coords(&active_coords);
+        }
+        assert_eq!(active_coords.len(), 848);
+    }
+
+    struct Recorder(std::cell::RefCell<Vec<u64>>);
+
+    impl aoc_common::ProgressReporter for Recorder {
+        fn report(&self, progress: &aoc_common::Progress) {
+            self.0.borrow_mut().push(progress.done);
+        }
+    }
+
+    #[test]
+    fn test_cycle_progress() {
+        let initial = read_plane("example1").unwrap();
+        let recorder = std::rc::Rc::new(Recorder(std::cell::RefCell::new(Vec::new())));
+        let active_coords = aoc_common::with_progress(recorder.clone(), || run_cycles(&initial, 3, 2)).unwrap();
+        assert_eq!(active_coords, step_active_coords_in(&step_active_coords_in(&initial, 3), 3));
+        assert_eq!(*recorder.0.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_check_cycles() {
+        let initial = read_plane("example1").unwrap();
+        let solved = Day17::part1(&initial).unwrap();
+        assert_eq!(solved.certificate["cycles"][0].as_array().unwrap().len(), 11);
+        assert!(Day17::check(&initial, 1, &solved.certificate, "112").is_ok());
+
+        let err = Day17::check(&initial, 2, &solved.certificate, "112").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: [0, 1, -1, -1] is active after cycle 1");
+        let mut extra = solved.certificate.clone();
+        extra["cycles"][5].as_array_mut().unwrap().push(json!([50, 50, 0, 0]));
+        let err = Day17::check(&initial, 1, &extra, "113").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: [50, 50, 0, 0] is inactive after cycle 6");
+    }
+
+    #[test]
+    fn test_coord_iter() {
+        let seq: Vec<Coord> = NeighborCoords::new_abs_3d(1).collect();
+        assert_eq!(seq.len(), 26);
+    }
+
+    #[test]
+    fn test_bounding_cube() {
+        let mut coords: BTreeSet<Coord> = BTreeSet::new();
+        coords.insert(Coord::new_3d(-1, -1, -1));
+        coords.insert(Coord::new_3d(1, 1, 1));
+        let bounds1 = bounding_cube(&coords);
+        assert_eq!(bounds1.0, Coord::new_3d(-1, -1, -1));
+        assert_eq!(bounds1.1, Coord::new_3d(1, 1, 1));
+        coords.insert(Coord::new_3d(-2, -2, -2));
+        coords.insert(Coord::new_3d(2, 2, 2));
+        let bounds2 = bounding_cube(&coords);
+        assert_eq!(bounds2.0, Coord::new_3d(-2, -2, -2));
+        assert_eq!(bounds2.1, Coord::new_3d(2, 2, 2));
+    }
+}
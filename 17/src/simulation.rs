@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+
+use aoc_common::{Error, Grid, Simulation};
+
+use crate::{bounding_cube, parse_plane, step_active_coords_in, Coord, Cube};
+
+/// The boot cycles of the pocket dimension, drawn as the z slices at w = 0.
+pub struct CubeSimulation {
+    active: BTreeSet<Coord>,
+    dimensions: u32,
+    cycle: u32,
+    cycles: u32,
+}
+
+impl CubeSimulation {
+    pub fn new(initial: BTreeSet<Coord>, dimensions: u32, cycles: u32) -> CubeSimulation {
+        CubeSimulation{ active: initial, dimensions: dimensions, cycle: 0, cycles: cycles }
+    }
+}
+
+impl Simulation for CubeSimulation {
+    fn step(&mut self) -> bool {
+        if self.cycle == self.cycles {
+            return false;
+        }
+        self.active = step_active_coords_in(&self.active, self.dimensions);
+        self.cycle = self.cycle + 1;
+        return true;
+    }
+
+    fn render(&self) -> String {
+        let (min, max) = bounding_cube(&self.active);
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut slices = Vec::new();
+        for z in min.z..(max.z + 1) {
+            let mut slice = Grid::new(width, height, Cube::Inactive);
+            for c in self.active.iter().filter(|c| c.z == z && c.w == 0) {
+                slice[((c.x - min.x) as usize, (c.y - min.y) as usize)] = Cube::Active;
+            }
+            let label = if self.dimensions == 4 { format!("z={}, w=0", z) } else { format!("z={}", z) };
+            slices.push(format!("{}\n{}", label, slice));
+        }
+        return slices.join("\n");
+    }
+
+    fn status(&self) -> String {
+        return format!("cycle {}/{}: {} active cubes", self.cycle, self.cycles, self.active.len());
+    }
+}
+
+/// Six boot cycles from the plane in `text`, in 3 dimensions for part 1 and 4 for part 2.
+pub fn simulate(text: &str, part: u8) -> Result<Box<dyn Simulation>, Error> {
+    let initial = parse_plane(text)?;
+    return match part {
+        1 => Ok(Box::new(CubeSimulation::new(initial, 3, 6))),
+        2 => Ok(Box::new(CubeSimulation::new(initial, 4, 6))),
+        _ => Err(Error::Unsolved),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_cycle() {
+        let mut sim = simulate(".#.\n..#\n###\n", 1).unwrap();
+        assert_eq!(sim.render(), "z=0\n.#.\n..#\n###\n");
+        assert!(sim.step());
+        assert_eq!(sim.render(), "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n");
+        assert_eq!(sim.status(), "cycle 1/6: 11 active cubes");
+        while sim.step() {}
+        assert_eq!(sim.status(), "cycle 6/6: 112 active cubes");
+    }
+}
@@ -0,0 +1,25 @@
+use std::collections::BTreeSet;
+
+use aoc17::{Coord, NeighborCoords};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn neighbors_are_distinct_nonzero_offsets(range in 1_i32..=3, four_d in any::<bool>()) {
+        let (neighbors, dimensions) = if four_d {
+            (NeighborCoords::new_abs_4d(range), 4)
+        } else {
+            (NeighborCoords::new_abs_3d(range), 3)
+        };
+        let offsets: Vec<Coord> = neighbors.collect();
+        let distinct: BTreeSet<&Coord> = offsets.iter().collect();
+
+        prop_assert_eq!(offsets.len(), ((2 * range + 1) as usize).pow(dimensions) - 1);
+        prop_assert_eq!(distinct.len(), offsets.len());
+        for c in &offsets {
+            prop_assert!(c.x != 0 || c.y != 0 || c.z != 0 || c.w != 0);
+            prop_assert!(c.x.abs() <= range && c.y.abs() <= range && c.z.abs() <= range && c.w.abs() <= range);
+            prop_assert!(four_d || c.w == 0);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use log::trace;
+
+use aoc_common::{certificate_field, check_answer, json, read_lines, to_lines, Certificate, Certified, Error, InputSource, ParseError, Solution, Unsolved};
+
+// Part 1: sum is 8929569623593
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    return read_lines(source);
+}
+
+fn error_at(chars: &Vec<char>, offset: usize, expected: &str) -> ParseError {
+    let text: String = chars.iter().collect();
+    let byte_offset: usize = chars[..offset].iter().map(|ch| ch.len_utf8()).sum();
+    return ParseError::at(&text, byte_offset, expected);
+}
+
+// Applies `op` unless the result would overflow.
+fn apply_op(op: char, val: i128, operand: i128) -> Option<i128> {
+    let result = if op == '*' { val.checked_mul(operand) } else { val.checked_add(operand) };
+    if let Some(x) = result {
+        trace!("{} = {} {} {}", x, val, op, operand);
+    }
+    return result;
+}
+
+const TOO_LARGE: &str = "an operand that keeps the value within 128 bits";
+
+pub fn eval_ltr_chars(chars: &Vec<char>, offset: &mut usize) -> Result<i128, ParseError>
+{
+    let mut val: i128 = 0;
+    let mut curr_op = '+';
+
+    while *offset < chars.len() {
+        let ch = chars[*offset];
+        match ch {
+            '(' => {
+                let start = *offset;
+                *offset += 1;
+                let sub_val = eval_ltr_chars(chars, offset)?;
+                val = apply_op(curr_op, val, sub_val).ok_or_else(|| error_at(chars, start, TOO_LARGE))?;
+                if *offset >= chars.len() {
+                    return Err(error_at(chars, *offset, "`)`"));
+                }
+            },
+            ')' => {
+                return Ok(val);
+            },
+            '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
+                let next_val: i128 = (ch as i128) - ('0' as i128);
+                val = apply_op(curr_op, val, next_val).ok_or_else(|| error_at(chars, *offset, TOO_LARGE))?;
+            },
+            '*' | '+' => curr_op = ch,
+            ' ' => { /* no-op */ },
+            _ => return Err(error_at(chars, *offset, "a digit, operator or parenthesis")),
+        }
+        *offset += 1;
+    }
+    return Ok(val);
+}
+
+pub fn eval_ltr(text: &String) -> Result<i128, ParseError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut offset = 0;
+    let val = eval_ltr_chars(&chars, &mut offset)?;
+    if offset < chars.len() {
+        // Only an unmatched `)` stops evaluation early.
+        return Err(error_at(&chars, offset, "end of line"));
+    }
+    return Ok(val);
+}
+
+// Evaluates with an explicit stack of the values and operators waiting on each
+// open parenthesis, rather than recursing like `eval_ltr_chars`.
+fn eval_with_stack(text: &str) -> Option<i128> {
+    let mut stack: Vec<(i128, char)> = Vec::new();
+    let (mut val, mut op) = (0_i128, '+');
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let operand = match ch {
+            ' ' => continue,
+            '+' | '*' => { op = ch; continue; },
+            '(' => {
+                stack.push((val, op));
+                (val, op) = (0, '+');
+                continue;
+            },
+            ')' => {
+                let inner = val;
+                (val, op) = stack.pop()?;
+                inner
+            },
+            '0'..='9' => {
+                let mut number = ch.to_digit(10)? as i128;
+                while let Some(digit) = chars.peek().and_then(|next| next.to_digit(10)) {
+                    number = number.checked_mul(10)?.checked_add(digit as i128)?;
+                    chars.next();
+                }
+                number
+            },
+            _ => return None,
+        };
+        val = if op == '*' { val.checked_mul(operand)? } else { val.checked_add(operand)? };
+    }
+    if !stack.is_empty() {
+        return None;
+    }
+    return Some(val);
+}
+
+/// Confirms that each line evaluates to its entry in the certificate's `values`,
+/// and that they add up to `answer`.
+pub fn check_values(lines: &Vec<String>, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    // Strings, as values can be too big for JSON numbers.
+    let values: Vec<String> = certificate_field(certificate, "values")?;
+    if values.len() != lines.len() {
+        return Err(Error::Certificate(format!("{} values for {} lines", values.len(), lines.len())));
+    }
+    let mut sum: i128 = 0;
+    for (idx, (ln, value)) in lines.iter().zip(&values).enumerate() {
+        let val = eval_with_stack(ln).ok_or_else(|| Error::Certificate(format!("line {} does not evaluate", idx + 1)))?;
+        if val.to_string() != *value {
+            return Err(Error::Certificate(format!("line {} evaluates to {}, not {}", idx + 1, val, value)));
+        }
+        sum = sum.checked_add(val).ok_or_else(|| Error::Certificate("the values add up to more than 128 bits".to_string()))?;
+    }
+    check_answer(sum, answer)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<String>;
+    type Answer1 = i128;
+    type Answer2 = Unsolved;
+
+    fn parse(text: &str) -> Result<Vec<String>, Error> {
+        Ok(to_lines(text))
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(lines: &Vec<String>) -> Result<Certified<i128>, Error> {
+        let mut sum: i128 = 0;
+        let mut values: Vec<String> = Vec::new();
+        for (idx, ln) in lines.iter().enumerate() {
+            let val = eval_ltr(ln).map_err(|err| err.on_line(idx + 1))?;
+            values.push(val.to_string());
+            sum = sum + val;
+        }
+        Ok(Certified::new(sum, json!({ "values": values })))
+    }
+
+    fn part2(_lines: &Vec<String>) -> Result<Certified<Unsolved>, Error> {
+        Err(Error::Unsolved)
+    }
+
+    fn check(lines: &Vec<String>, _part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_values(lines, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_basic_0() {
+        let expr_text = "(2 * 3)";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 6);
+    }
+
+    #[test]
+    fn test_example_0() {
+        let expr_text = "1 + (2 * 3) + (4 * (5 + 6))";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 51);
+    }
+
+    #[test]
+    fn test_example_1() {
+        let expr_text = "2 * 3 + (4 * 5)";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 26);
+    }
+
+    #[test]
+    fn test_example_2() {
+        let expr_text = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 437);
+    }
+
+    #[test]
+    fn test_example_3() {
+        let expr_text = "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 12240);
+    }
+
+    #[test]
+    fn test_example_4() {
+        let expr_text = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 13632);
+    }
+
+    #[test]
+    fn test_example_real_last() {
+        let expr_text = "4 + (8 * (4 + 5 + 5) + (4 + 8 * 5 + 2 * 5 * 8) + 2 * 9) * 9";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 210150);
+    }
+
+    #[test]
+    fn test_example_real_random() {
+        let expr_text = "7 * 8 + (4 + 2 + (8 + 9 * 7 * 3 + 6 * 9)) + 5 * 7";
+        let result = eval_ltr(&expr_text.to_string()).unwrap();
+        assert_eq!(result, 23338);
+    }
+
+    #[test]
+    fn test_check_values() {
+        let lines = Day18::parse("2 * 3 + (4 * 5)\n5 + (8 * 3 + 9 + 3 * 4 * 3)\n").unwrap();
+        let solved = Day18::part1(&lines).unwrap();
+        assert_eq!(solved.certificate, json!({ "values": ["26", "437"] }));
+        assert!(Day18::check(&lines, 1, &solved.certificate, "463").is_ok());
+
+        let err = Day18::check(&lines, 1, &json!({ "values": ["26", "1445"] }), "1471").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: line 2 evaluates to 437, not 1445");
+        let err = Day18::check(&lines, 1, &json!({ "values": ["26"] }), "26").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: 1 values for 2 lines");
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let err = eval_ltr(&"1 + (2 * 3".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (11, None));
+        let err = eval_ltr(&"1 + 2) * 3".to_string()).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (6, "end of line"));
+        let err = eval_ltr(&"1 - 2".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (3, Some("-".to_string())));
+
+        let err = eval_ltr(&vec!["9"; 50].join(" * ")).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (161, TOO_LARGE));
+    }
+}
@@ -0,0 +1,60 @@
+use aoc18::eval_ltr;
+use proptest::prelude::*;
+
+// Evaluates with `+` and `*` at equal precedence, stacking the outer value at each `(`.
+fn reference_eval(expr: &str) -> i128 {
+    let mut stack: Vec<(i128, char)> = Vec::new();
+    let (mut val, mut op) = (0_i128, '+');
+    for ch in expr.chars().filter(|ch| *ch != ' ') {
+        let operand = match ch {
+            '+' | '*' => {
+                op = ch;
+                continue;
+            },
+            '(' => {
+                stack.push((val, op));
+                val = 0;
+                op = '+';
+                continue;
+            },
+            ')' => {
+                let inner = val;
+                let (outer_val, outer_op) = stack.pop().unwrap();
+                val = outer_val;
+                op = outer_op;
+                inner
+            },
+            _ => ch.to_digit(10).unwrap() as i128,
+        };
+        val = if op == '+' { val + operand } else { val * operand };
+    }
+    return val;
+}
+
+fn join_terms(terms: Vec<(String, bool)>) -> String {
+    let mut text = String::new();
+    for (idx, (term, add)) in terms.into_iter().enumerate() {
+        if idx > 0 {
+            text.push_str(if add { " + " } else { " * " });
+        }
+        text.push_str(&term);
+    }
+    return text;
+}
+
+// Single digits and parenthesized groups joined by random operators.
+fn expression() -> impl Strategy<Value = String> {
+    let digit = (1_u32..=9).prop_map(|d| d.to_string());
+    let term = digit.prop_recursive(3, 24, 4, |inner| {
+        prop::collection::vec((inner, any::<bool>()), 1..4)
+            .prop_map(|terms| format!("({})", join_terms(terms)))
+    });
+    return prop::collection::vec((term, any::<bool>()), 1..6).prop_map(join_terms);
+}
+
+proptest! {
+    #[test]
+    fn eval_ltr_matches_reference(expr in expression()) {
+        prop_assert_eq!(eval_ltr(&expr).unwrap(), reference_eval(&expr));
+    }
+}
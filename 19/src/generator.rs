@@ -0,0 +1,130 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+// Rules on level l match 2^l letters, and rule 0 a pair of the top level's rules.
+const LEVELS: usize = 4;
+const RULES_PER_LEVEL: usize = 3;
+
+enum GenRule {
+    Letter(u8),
+    Alts(Vec<Vec<usize>>),
+}
+
+fn derive(rules: &Vec<GenRule>, idx: usize, rng: &mut StdRng, msg: &mut Vec<u8>) {
+    match &rules[idx] {
+        GenRule::Letter(letter) => msg.push(*letter),
+        GenRule::Alts(alts) => {
+            for &sub in alts.choose(rng).unwrap() {
+                derive(rules, sub, rng, msg);
+            }
+        },
+    }
+}
+
+// Every offset in `msg` at which a match of rule `idx` starting at `start` can end.
+fn match_ends(rules: &Vec<GenRule>, idx: usize, msg: &[u8], start: usize) -> Vec<usize> {
+    match &rules[idx] {
+        GenRule::Letter(letter) if msg.get(start) == Some(letter) => vec![start + 1],
+        GenRule::Letter(_) => vec![],
+        GenRule::Alts(alts) => {
+            let mut ends: Vec<usize> = Vec::new();
+            for alt in alts {
+                let mut offsets = vec![start];
+                for &sub in alt {
+                    offsets = offsets.iter().flat_map(|&o| match_ends(rules, sub, msg, o)).collect();
+                }
+                ends.extend(offsets);
+            }
+            ends
+        },
+    }
+}
+
+fn build_rules(rng: &mut StdRng) -> Vec<GenRule> {
+    let mut rules: Vec<GenRule> = vec![GenRule::Alts(vec![]), GenRule::Letter(b'a'), GenRule::Letter(b'b')];
+    let mut prev_level: Vec<usize> = vec![1, 2];
+    for _ in 0..LEVELS {
+        let mut level: Vec<usize> = Vec::new();
+        for _ in 0..RULES_PER_LEVEL {
+            let alts = (0..rng.gen_range(1..=2))
+                .map(|_| vec![*prev_level.choose(rng).unwrap(), *prev_level.choose(rng).unwrap()])
+                .collect();
+            level.push(rules.len());
+            rules.push(GenRule::Alts(alts));
+        }
+        prev_level = level;
+    }
+    rules[0] = GenRule::Alts(vec![vec![*prev_level.choose(rng).unwrap(), *prev_level.choose(rng).unwrap()]]);
+    return rules;
+}
+
+// The puzzle text and how many of its messages match rule 0.
+fn generate_puzzle(size: usize, seed: u64) -> (String, usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let rules = build_rules(&mut rng);
+
+    // Number the rules at random, keeping rule 0 first in numbering but not in the listing.
+    let mut numbers: Vec<usize> = (1..rules.len()).collect();
+    numbers.shuffle(&mut rng);
+    numbers.insert(0, 0);
+    let mut lines: Vec<String> = rules.iter().enumerate().map(|(idx, rule)| {
+        let body = match rule {
+            GenRule::Letter(letter) => format!("\"{}\"", *letter as char),
+            GenRule::Alts(alts) => alts.iter()
+                .map(|alt| alt.iter().map(|&sub| numbers[sub].to_string()).collect::<Vec<String>>().join(" "))
+                .collect::<Vec<String>>()
+                .join(" | "),
+        };
+        format!("{}: {}", numbers[idx], body)
+    }).collect();
+    lines.shuffle(&mut rng);
+
+    let mut text = lines.join("\n") + "\n\n";
+    let mut matching = 0;
+    for _ in 0..size {
+        let mut msg: Vec<u8> = Vec::new();
+        match rng.gen_range(0..3) {
+            0 => derive(&rules, 0, &mut rng, &mut msg),
+            1 => {
+                // A near miss, which may still match.
+                derive(&rules, 0, &mut rng, &mut msg);
+                let idx = rng.gen_range(0..msg.len());
+                msg[idx] = if msg[idx] == b'a' { b'b' } else { b'a' };
+            },
+            _ => {
+                let len = rng.gen_range(1..=(4 << LEVELS));
+                msg = (0..len).map(|_| if rng.gen_bool(0.5) { b'a' } else { b'b' }).collect();
+            },
+        }
+        if match_ends(&rules, 0, &msg, 0).contains(&msg.len()) {
+            matching = matching + 1;
+        }
+        text.push_str(&String::from_utf8(msg).unwrap());
+        text.push('\n');
+    }
+    return (text, matching);
+}
+
+/// A random grammar of `a`/`b` rules followed by `size` messages, about half of
+/// which match rule 0. Every way through a rule matches the same number of letters.
+pub fn generate(size: usize, seed: u64) -> String {
+    return generate_puzzle(size, seed).0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_puzzle;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            let (text, matching) = generate_puzzle(300, seed);
+            let puz = parse_puzzle(&text).unwrap();
+            assert_eq!(puz.exprs.len(), 300);
+            assert!(matching > 0 && matching < 300);
+            assert_eq!(puz.count_valid().unwrap(), matching, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,429 @@
+pub mod generator;
+
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_common::combinator::{alt, anychar, char, consumed, cut, delimited, digit1, expect, literal, map, parse_line, recognize, separated_list1, separated_pair, space0, space1, unsigned, PResult};
+use aoc_common::{certificate_field, check_answer, count, json, read_parsed, Certificate, Certified, Iterations, split_sections, Error, InputSource, ParseError, Section, Solution, Unsolved};
+
+// Part 1: valid count = 248
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Rule {
+    Alt{ choices: Vec<Rule> },
+    Seq{ rules: Vec<String> },
+    Val{ letter: String },
+}
+
+pub struct Puzzle {
+    pub rules: HashMap<String, Rule>,
+    pub exprs: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn check_validity(&self, subject: &String, iterations: &mut Iterations) -> Result<bool, Error> {
+        return self.check_validity_traced(subject, iterations, &mut Vec::new());
+    }
+
+    /// Checks a message like `check_validity`, recording which choice each
+    /// alternative in its derivation took.
+    pub fn check_validity_traced(&self, subject: &String, iterations: &mut Iterations, choices: &mut Vec<usize>) -> Result<bool, Error> {
+        let rule0 = self.rules.get(&String::from("0")).expect("Rule zero.");
+        let (valid_prefix, remaining) = check_rule(&self.rules, rule0, subject, iterations, 0, choices)?;
+        return Ok(valid_prefix && remaining.is_empty());
+    }
+
+    pub fn count_valid(&self) -> Result<usize, Error> {
+        return Ok(self.valid_derivations()?.len());
+    }
+
+    /// The index of each valid message, with the choices its derivation took.
+    pub fn valid_derivations(&self) -> Result<Vec<(usize, Vec<usize>)>, Error> {
+        let mut iterations = Iterations::checking_every(1024);
+        let mut valid = Vec::new();
+        for (idx, expr) in self.exprs.iter().enumerate() {
+            let mut choices = Vec::new();
+            if self.check_validity_traced(expr, &mut iterations, &mut choices)? {
+                valid.push((idx, choices));
+            }
+        }
+        return Ok(valid);
+    }
+}
+
+fn rule_number(input: &str) -> PResult<'_, &str> {
+    return expect("a rule number", recognize(unsigned::<u32>()))(input);
+}
+
+// Either a quoted letter or a sequence of rule numbers, with any spaces around it.
+fn rule_choice(input: &str) -> PResult<'_, Rule> {
+    let letter = map(delimited(char('"'), anychar, char('"')), |ch| Rule::Val{ letter: ch.to_string() });
+    let seq = map(separated_list1(space1, rule_number), |keys| Rule::Seq{ rules: keys.iter().map(|k| k.to_string()).collect() });
+    return delimited(space0, expect("a rule number", alt((letter, seq))), space0)(input);
+}
+
+fn rule_body(input: &str) -> PResult<'_, Rule> {
+    let (rest, mut alt_rules) = separated_list1(char('|'), cut(rule_choice))(input)?;
+    if alt_rules.len() == 1 {
+        return Ok((rest, alt_rules.remove(0)));
+    } else {
+        return Ok((rest, Rule::Alt{
+            choices: alt_rules,
+        }));
+    }
+}
+
+pub fn parse_rule(text: &String) -> Result<Rule, ParseError> {
+    return parse_line(text, rule_body);
+}
+
+// The rules are the section on the first line, if any.
+fn rule_section(contents: &str) -> Option<Section<'_>> {
+    return split_sections(contents).into_iter().next().filter(|section| section.line == 1);
+}
+
+// Rules run up to the first blank line. Every rule they refer to, and rule 0, must be defined.
+pub fn read_rules(contents: &str) -> Result<HashMap<String, Rule>, ParseError> {
+    let section = rule_section(contents);
+    let mut rules: HashMap<String, Rule> = HashMap::new();
+    let mut rule_lines: Vec<(usize, &str, &str)> = Vec::new();
+    for (line_no, ln) in section.iter().flat_map(|section| section.lines()) {
+        let numbered_rule = separated_pair(expect("a rule like `0: 1 2`", digit1), literal(": "), consumed(rule_body));
+        let (key, (body, rule)) = parse_line(ln, numbered_rule).map_err(|err| err.on_line(line_no))?;
+        rules.insert(key.to_string(), rule);
+        rule_lines.push((line_no, ln, body));
+    }
+
+    for (line_no, ln, body) in rule_lines {
+        if body.trim().starts_with('"') {
+            continue;
+        }
+        for k in body.split(|c: char| c.is_whitespace() || c == '|').filter(|k| k.len() > 0) {
+            if !rules.contains_key(k) {
+                return Err(ParseError::at_token(ln, k, "a defined rule number").on_line(line_no));
+            }
+        }
+    }
+
+    if !rules.contains_key("0") {
+        let end = section.map(|section| section.text.len()).unwrap_or(0);
+        return Err(ParseError::at(contents, end, "a definition of rule 0"));
+    }
+
+    return Ok(rules);
+}
+
+// Messages follow the blank line after the rules.
+pub fn read_exprs(contents: &str) -> Result<Vec<String>, ParseError> {
+    let skip = if rule_section(contents).is_some() { 1 } else { 0 };
+    let exprs: Vec<String> = split_sections(contents).iter()
+        .skip(skip)
+        .flat_map(|section| section.lines())
+        .map(|(line_no, ln)| {
+            match ln.char_indices().find(|(_, ch)| !ch.is_ascii_alphabetic()) {
+                Some((offset, _)) => Err(ParseError::at(ln, offset, "a letter").on_line(line_no)),
+                None => Ok(ln.to_string()),
+            }
+        })
+        .collect::<Result<Vec<String>, ParseError>>()?;
+    Ok(exprs)
+}
+
+pub fn read_puzzle<S: Into<InputSource>>(source: S) -> Result<Puzzle, Error> {
+    return read_parsed(source, parse_puzzle);
+}
+
+pub fn parse_puzzle(contents: &str) -> Result<Puzzle, ParseError> {
+    let rules = read_rules(contents)?;
+    let exprs = read_exprs(contents)?;
+    Ok(Puzzle{
+        rules: rules,
+        exprs: exprs,
+    })
+}
+
+// Each call is an iteration, as rules that refer back to themselves can take a very long time.
+pub fn check_validity(rules: &HashMap<String, Rule>, rule: &Rule, subject: &String, iterations: &mut Iterations) -> Result<(bool, String), Error> {
+    return check_rule(rules, rule, subject, iterations, 0, &mut Vec::new());
+}
+
+// `stalled` counts the calls around this one made without matching a letter. Every rule
+// adds at most two, a choice and a sequence, so any more means a rule is its own prefix.
+fn check_rule(rules: &HashMap<String, Rule>, rule: &Rule, subject: &String, iterations: &mut Iterations, stalled: usize, taken: &mut Vec<usize>) -> Result<(bool, String), Error> {
+    count("rule_invocations", 1);
+    iterations.tick()?;
+    if stalled > 2 * rules.len() {
+        return Err(Error::NoAnswer("a rule starts with itself, so checking it never ends".to_string()));
+    }
+    match rule {
+        Rule::Val{ letter, .. } => {
+            if subject.starts_with(letter.as_str()) {
+                return Ok((true, subject[1..].to_string()));
+            } else {
+                return Ok((false, subject.clone()));
+            }
+        },
+        Rule::Seq{ rules: sub_rules, .. } => {
+            let mut subject1: String = subject.clone();
+            let chosen = taken.len();
+            for k in sub_rules {
+                let rule1 = rules.get(k).expect("Known rule.");
+                let inner_stalled = if subject1.len() == subject.len() { stalled + 1 } else { 0 };
+                let (success, remaining) = check_rule(rules, rule1, &subject1, iterations, inner_stalled, taken)?;
+                if success {
+                    subject1 = remaining;
+                } else {
+                    taken.truncate(chosen);
+                    return Ok((false, subject.clone()));
+                }
+            }
+            return Ok((true, subject1));
+        },
+        Rule::Alt{ choices, .. } => {
+            for (idx, r) in choices.iter().enumerate() {
+                taken.push(idx);
+                let (success, remaining) = check_rule(rules, r, subject, iterations, stalled + 1, taken)?;
+                if success {
+                    return Ok((true, remaining));
+                }
+                taken.pop();
+            }
+            return Ok((false, subject.clone()));
+        },
+    }
+}
+
+// Replays a derivation, taking the next of `choices` at each alternative,
+// and returns where in `text` it leaves off.
+fn replay(rules: &HashMap<String, Rule>, rule: &Rule, text: &str, pos: usize, choices: &mut std::slice::Iter<usize>) -> Option<usize> {
+    match rule {
+        Rule::Val{ letter } => {
+            return if text[pos..].starts_with(letter.as_str()) { Some(pos + letter.len()) } else { None };
+        },
+        Rule::Seq{ rules: sub_rules } => {
+            let mut end = pos;
+            for k in sub_rules {
+                end = replay(rules, rules.get(k)?, text, end, choices)?;
+            }
+            return Some(end);
+        },
+        Rule::Alt{ choices: alternatives } => {
+            let choice = choices.next()?;
+            return replay(rules, alternatives.get(*choice)?, text, pos, choices);
+        },
+    }
+}
+
+// Every position the rule can end at when started at `pos`, trying all choices
+// rather than taking the first that fits as `check_rule` does. Rule 0 matches a
+// message when its length is among the ends from position 0.
+fn rule_ends(rules: &HashMap<String, Rule>, rule: &Rule, text: &str, pos: usize, memo: &mut HashMap<(String, usize), Option<BTreeSet<usize>>>) -> Result<BTreeSet<usize>, Error> {
+    match rule {
+        Rule::Val{ letter } => {
+            return Ok(text[pos..].starts_with(letter.as_str()).then(|| pos + letter.len()).into_iter().collect());
+        },
+        Rule::Seq{ rules: sub_rules } => {
+            let mut ends: BTreeSet<usize> = BTreeSet::from([pos]);
+            for k in sub_rules {
+                let mut next_ends = BTreeSet::new();
+                for end in ends {
+                    match memo.get(&(k.clone(), end)) {
+                        Some(Some(known)) => next_ends.extend(known),
+                        // Every rule matches at least one letter, so only a rule
+                        // starting with itself comes back to the same position.
+                        Some(None) => return Err(Error::Certificate(format!("rule {} starts with itself", k))),
+                        None => {
+                            memo.insert((k.clone(), end), None);
+                            let found = rule_ends(rules, &rules[k], text, end, memo)?;
+                            next_ends.extend(&found);
+                            memo.insert((k.clone(), end), Some(found));
+                        },
+                    }
+                }
+                ends = next_ends;
+            }
+            return Ok(ends);
+        },
+        Rule::Alt{ choices } => {
+            let mut ends = BTreeSet::new();
+            for r in choices {
+                ends.extend(rule_ends(rules, r, text, pos, memo)?);
+            }
+            return Ok(ends);
+        },
+    }
+}
+
+/// Confirms that each message listed in the certificate's `valid` is derived from
+/// rule 0 by the choices listed with it, that no other message matches rule 0, and
+/// that `answer` messages are listed.
+pub fn check_derivations(puz: &Puzzle, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let valid: Vec<(usize, Vec<usize>)> = certificate_field(certificate, "valid")?;
+    let mut listed: BTreeSet<usize> = BTreeSet::new();
+    let rule0 = &puz.rules["0"];
+    for (idx, choices) in &valid {
+        let expr = puz.exprs.get(*idx).ok_or_else(|| Error::Certificate(format!("there is no message {}", idx)))?;
+        if !listed.insert(*idx) {
+            return Err(Error::Certificate(format!("message {} is listed twice", idx)));
+        }
+        let mut remaining = choices.iter();
+        let end = replay(&puz.rules, rule0, expr, 0, &mut remaining);
+        if end != Some(expr.len()) || remaining.next().is_some() {
+            return Err(Error::Certificate(format!("the choices for message {} do not derive it", idx)));
+        }
+    }
+    for (idx, expr) in puz.exprs.iter().enumerate() {
+        if !listed.contains(&idx) && rule_ends(&puz.rules, rule0, expr, 0, &mut HashMap::new())?.contains(&expr.len()) {
+            return Err(Error::Certificate(format!("message {} matches, but is not listed", idx)));
+        }
+    }
+    check_answer(listed.len(), answer)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Puzzle;
+    type Answer1 = usize;
+    type Answer2 = Unsolved;
+
+    fn parse(text: &str) -> Result<Puzzle, Error> {
+        Ok(parse_puzzle(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(puz: &Puzzle) -> Result<Certified<usize>, Error> {
+        let valid = puz.valid_derivations()?;
+        Ok(Certified::new(valid.len(), json!({ "valid": valid })))
+    }
+
+    fn part2(_puz: &Puzzle) -> Result<Certified<Unsolved>, Error> {
+        Err(Error::Unsolved)
+    }
+
+    fn check(puz: &Puzzle, _part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_derivations(puz, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::CancelToken;
+
+    #[test]
+    fn test_example_rules() {
+        let puz = read_puzzle("example").unwrap();
+        assert_eq!(puz.rules.len(), 6);
+
+        let rule0 = puz.rules.get(&String::from("0")).unwrap();
+        assert_eq!(*rule0, Rule::Seq{ rules: vec![
+            String::from("4"), String::from("1"), String::from("5"),
+        ]});
+
+        let rule5 = puz.rules.get(&String::from("5")).unwrap();
+        assert_eq!(*rule5, Rule::Val{ letter: String::from("b") });
+
+        assert_eq!(puz.exprs[0], "ababbb");
+
+        let (validity_good0, _) = check_validity(&puz.rules, rule0, &String::from("ababbb"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_good0, true);
+
+        let (validity_good1, _) = check_validity(&puz.rules, rule0, &String::from("abbbab"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_good1, true);
+
+        let (validity_bad0, _) = check_validity(&puz.rules, rule0, &String::from("bababa"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_bad0, false);
+
+        let (validity_bad1, _) = check_validity(&puz.rules, rule0, &String::from("aaabbb"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_bad1, false);
+
+        let (validity_bad2, remaining) = check_validity(&puz.rules, rule0, &String::from("aaaabbb"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_bad2, true);
+        assert_eq!(remaining, String::from("b"));
+
+        let validity_bad2_prime = puz.check_validity(&String::from("aaaabbb"), &mut Iterations::new()).unwrap();
+        assert_eq!(validity_bad2_prime, false);
+    }
+
+    #[test]
+    fn test_parse_rule_val() {
+        let rule = parse_rule(&String::from("\"a\"")).unwrap();
+        assert_eq!(rule, Rule::Val{ letter: "a".to_string() });
+    }
+
+    #[test]
+    fn test_parse_rule_seq() {
+        let rule = parse_rule(&String::from("1 2")).unwrap();
+        assert_eq!(rule, Rule::Seq{ rules: vec![
+            String::from("1"),
+            String::from("2"),
+        ]});
+    }
+
+    #[test]
+    fn test_parse_rule_alt() {
+        let rule = parse_rule(&String::from("1 2 | 2 1")).unwrap();
+        assert_eq!(rule, Rule::Alt{ choices: vec![
+            Rule::Seq{ rules: vec![
+                String::from("1"),
+                String::from("2"),
+            ]},
+            Rule::Seq{ rules: vec![
+                String::from("2"),
+                String::from("1"),
+            ]},
+        ]});
+    }
+
+    #[test]
+    fn test_recursive_rules() {
+        let puz = parse_puzzle("0: 1 0 | 1\n1: \"a\"\n\naaa\nab\n").unwrap();
+        assert_eq!(puz.count_valid().unwrap(), 1);
+
+        let puz = parse_puzzle("0: 0 1 | 1\n1: \"a\"\n\naa\n").unwrap();
+        let err = puz.count_valid().unwrap_err();
+        assert_eq!(err.to_string(), "no answer: a rule starts with itself, so checking it never ends");
+
+        let token = CancelToken::new();
+        token.cancel();
+        let puz = parse_puzzle(&format!("0: 1 0 | 1\n1: \"a\"\n\n{}\n", "a".repeat(1000))).unwrap();
+        let err = token.run(|| puz.count_valid()).unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 1024 iterations");
+    }
+
+    #[test]
+    fn test_check_derivations() {
+        let puz = read_puzzle("example").unwrap();
+        let solved = Day19::part1(&puz).unwrap();
+        assert_eq!(solved.certificate, json!({ "valid": [[0, [1, 1, 1]], [2, [0, 1, 1]]] }));
+        assert!(Day19::check(&puz, 1, &solved.certificate, "2").is_ok());
+
+        let err = Day19::check(&puz, 1, &json!({ "valid": [[0, [1, 1, 1]]] }), "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: message 2 matches, but is not listed");
+        let err = Day19::check(&puz, 1, &json!({ "valid": [[0, [1, 1, 0]], [2, [0, 1, 1]]] }), "2").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the choices for message 0 do not derive it");
+        let err = Day19::check(&puz, 1, &json!({ "valid": [[0, [1, 1, 1]], [2, [0, 1, 1]], [2, [0, 1, 1]]] }), "3").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: message 2 is listed twice");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_rule(&String::from("1 2 | ")).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (7, "a rule number"));
+        let err = parse_rule(&String::from("\"ab\"")).err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (3, "`\"`"));
+        let err = parse_puzzle("0: 1
+1: 2 x
+").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 6, "the end of the line"));
+        let err = parse_puzzle("0 1
+").err().unwrap();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 2, "`: `"));
+        let err = parse_puzzle("0: 1 2\n1: \"a\"\n\na\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found), (1, 6, Some("2".to_string())));
+        let err = parse_puzzle("0: 1\n1: \"a\"\n\nab \n").err().unwrap();
+        assert_eq!((err.line, err.column), (4, 3));
+    }
+}
@@ -0,0 +1,42 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn random_letter(rng: &mut StdRng) -> char {
+    return rng.gen_range(b'a'..=b'z') as char;
+}
+
+/// `size` random policy and password lines. The policy letter is common in
+/// passwords, so plenty of them conform to one policy or the other.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut text = String::new();
+    for _ in 0..size {
+        let letter = random_letter(&mut rng);
+        let len = rng.gen_range(1..=20);
+        let min = rng.gen_range(1..=len);
+        let max = rng.gen_range(min..=len);
+        let password: String = (0..len)
+            .map(|_| if rng.gen_bool(0.4) { letter } else { random_letter(&mut rng) })
+            .collect();
+        text.push_str(&format!("{}-{} {}: {}\n", min, max, letter, password));
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{evaluate_entries, parse_entries};
+
+    #[test]
+    fn test_generate() {
+        let text = generate(200, 7);
+        assert_eq!(text, generate(200, 7));
+
+        let entries = parse_entries(&text).unwrap();
+        let (freq_results, pos_results) = evaluate_entries(&entries);
+        assert_eq!(freq_results.eval_count, 200);
+        assert!(freq_results.success_count() > 0 && freq_results.error_count > 0);
+        assert!(pos_results.success_count() > 0 && pos_results.error_count > 0);
+    }
+}
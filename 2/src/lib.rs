@@ -0,0 +1,250 @@
+pub mod generator;
+
+use std::str;
+
+use aoc_common::combinator::{anychar, char, expect, literal, parse_line, take_while1, terminated, tuple, unsigned};
+use aoc_common::{certificate_indices, check_answer, json, parse_lines, read_parsed, Certificate, Certified, Details, Error, InputSource, ParseError, Solution};
+
+pub struct FreqPolicy {
+    pub min_count: u8,
+    pub max_count: u8,
+    pub focus_char: char,
+}
+
+pub struct PositionPolicy {
+    pub pos0: usize,
+    pub pos1: usize,
+    pub focus_char: char,
+}
+
+pub struct EvalResult {
+    pub eval_count: u32,
+    pub error_count: u32,
+}
+
+pub trait Policy {
+    fn check_conformance(&self, password: &str) -> bool;
+}
+
+pub fn make_eval_result() -> EvalResult {
+    return EvalResult{
+        eval_count: 0,
+        error_count: 0
+    };
+}
+
+impl EvalResult {
+    pub fn eval<F>(&mut self, callback: F) -> bool
+        where F: Fn() -> bool
+    {
+
+        self.eval_count = self.eval_count + 1;
+        let result = callback();
+        if !result {
+            self.error_count = self.error_count + 1;
+        }
+        return result;
+    }
+
+    pub fn success_count(&self) -> u32 {
+        return self.eval_count - self.error_count;
+    }
+
+    pub fn report(&self, prefix: &str) {
+        println!("{}{} evals with {} successes and {} errors",
+                 prefix,
+                 self.eval_count,
+                 self.success_count(),
+                 self.error_count);
+    }
+}
+
+pub struct PasswordEntry {
+    pub freq_policy: FreqPolicy,
+    pub pos_policy: PositionPolicy,
+    pub password: String,
+}
+
+pub fn parse_entry(ln: &str) -> Result<PasswordEntry, ParseError> {
+    // Both policies read the same two numbers, so positions are limited to 255 as well.
+    let count = || expect("a count up to 255", unsigned::<u8>());
+    let (first, second, focus_char, password) = parse_line(ln, tuple((
+        terminated(count(), char('-')),
+        terminated(count(), char(' ')),
+        terminated(expect("a letter", anychar), literal(": ")),
+        expect("a password", take_while1(|_| true)),
+    )))?;
+
+    return Ok(PasswordEntry{
+        freq_policy: make_freq_policy(first, second, focus_char),
+        pos_policy: make_position_policy(first as usize, second as usize, focus_char),
+        password: password.to_string(),
+    });
+}
+
+pub fn parse_entries(contents: &str) -> Result<Vec<PasswordEntry>, ParseError> {
+    return parse_lines(contents, parse_entry);
+}
+
+pub fn evaluate_entries(entries: &Vec<PasswordEntry>) -> (EvalResult, EvalResult) {
+    let mut freq_results = make_eval_result();
+    let mut pos_results = make_eval_result();
+
+    for entry in entries {
+        freq_results.eval(|| entry.freq_policy.check_conformance(&entry.password));
+        pos_results.eval(|| entry.pos_policy.check_conformance(&entry.password));
+    }
+
+    return (freq_results, pos_results);
+}
+
+pub fn check_passwords<S: Into<InputSource>>(source: S) -> Result<(EvalResult, EvalResult), Error> {
+    let entries = read_parsed(source, parse_entries)?;
+    return Ok(evaluate_entries(&entries));
+}
+
+pub fn make_position_policy(pos0: usize, pos1: usize, focus_char: char) -> PositionPolicy {
+    return PositionPolicy{
+        pos0: pos0,
+        pos1: pos1,
+        focus_char: focus_char,
+    };
+}
+
+impl Policy for PositionPolicy {
+    fn check_conformance(&self, password: &str) -> bool {
+        let mut matches = 0;
+
+        for (pos, ch) in password.char_indices() {
+            let norm_pos = pos + 1;
+            if (ch == self.focus_char) && ((norm_pos == self.pos0) || (norm_pos == self.pos1)) {
+                matches = matches + 1;
+            }
+        }
+
+        return matches == 1;
+    }
+}
+
+pub fn make_freq_policy(min_count: u8, max_count: u8, focus_char: char) -> FreqPolicy {
+    return FreqPolicy{
+        min_count: min_count,
+        max_count: max_count,
+        focus_char: focus_char,
+    };
+}
+
+impl Policy for FreqPolicy {
+    fn check_conformance(&self, password: &str) -> bool {
+        let mut count = 0;
+        for ch in password.chars() {
+            if ch == self.focus_char {
+                count = count + 1;
+            }
+        }
+        return (self.min_count <= count) && (count <= self.max_count);
+    }
+}
+
+
+/// Indices of the entries whose password conforms to `policy`.
+pub fn conforming_entries<P: Policy, F: Fn(&PasswordEntry) -> &P>(entries: &Vec<PasswordEntry>, policy: F) -> Vec<usize> {
+    return entries.iter()
+        .enumerate()
+        .filter(|(_, entry)| policy(entry).check_conformance(&entry.password))
+        .map(|(idx, _)| idx)
+        .collect();
+}
+
+// Restates both policies from the puzzle text rather than reusing `Policy`, so a
+// mistake there cannot vouch for itself.
+fn follows_policy(entry: &PasswordEntry, part: u8) -> bool {
+    if part == 1 {
+        let policy = &entry.freq_policy;
+        let count = entry.password.chars().filter(|ch| *ch == policy.focus_char).count();
+        return policy.min_count as usize <= count && count <= policy.max_count as usize;
+    }
+    let policy = &entry.pos_policy;
+    let has_focus_at = |pos: usize| pos > 0 && entry.password.chars().nth(pos - 1) == Some(policy.focus_char);
+    return has_focus_at(policy.pos0) != has_focus_at(policy.pos1);
+}
+
+/// Confirms that the entries listed as `valid` are exactly those following the part's policy.
+pub fn check_valid_entries(entries: &Vec<PasswordEntry>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let valid = certificate_indices(certificate, "valid", entries.len())?;
+    for (idx, entry) in entries.iter().enumerate() {
+        if follows_policy(entry, part) != valid.contains(&idx) {
+            let verdict = if valid.contains(&idx) { "breaks" } else { "follows" };
+            return Err(Error::Certificate(format!("entry {} {} the policy", idx, verdict)));
+        }
+    }
+    check_answer(valid.len(), answer)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PasswordEntry>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Vec<PasswordEntry>, Error> {
+        Ok(parse_entries(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(entries: &Vec<PasswordEntry>) -> Result<Certified<u32>, Error> {
+        let valid = conforming_entries(entries, |entry| &entry.freq_policy);
+        Ok(Certified::new(valid.len() as u32, json!({ "valid": valid })))
+    }
+
+    fn part2(entries: &Vec<PasswordEntry>) -> Result<Certified<u32>, Error> {
+        let valid = conforming_entries(entries, |entry| &entry.pos_policy);
+        Ok(Certified::new(valid.len() as u32, json!({ "valid": valid })))
+    }
+
+    fn details(entries: &Vec<PasswordEntry>, part: u8) -> Result<Details, Error> {
+        let (freq_results, pos_results) = evaluate_entries(entries);
+        let results = if part == 1 { freq_results } else { pos_results };
+        let mut details = Details::new();
+        details.insert("eval_count".to_string(), json!(results.eval_count));
+        details.insert("error_count".to_string(), json!(results.error_count));
+        Ok(details)
+    }
+
+    fn check(entries: &Vec<PasswordEntry>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_valid_entries(entries, part, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry("1-3 a: abcde").unwrap();
+        assert_eq!((entry.freq_policy.min_count, entry.pos_policy.pos1, entry.password.as_str()), (1, 3, "abcde"));
+
+        let err = parse_entry("1-300 a: abcde").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (3, "a count up to 255"));
+        let err = parse_entry("1-3 a abcde").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (6, "`: `"));
+        let err = parse_entry("1-3 a: ").err().unwrap();
+        assert_eq!((err.column, err.expected.as_str()), (8, "a password"));
+    }
+
+    #[test]
+    fn test_check_valid_entries() {
+        let entries = Day2::parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n").unwrap();
+        let solved = Day2::part2(&entries).unwrap();
+        assert_eq!(solved.certificate, json!({ "valid": [0] }));
+        assert!(Day2::check(&entries, 2, &solved.certificate, "1").is_ok());
+
+        let err = Day2::check(&entries, 1, &solved.certificate, "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: entry 2 follows the policy");
+        let err = Day2::check(&entries, 2, &json!({ "valid": [0, 2] }), "2").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: entry 2 breaks the policy");
+    }
+}
@@ -0,0 +1,152 @@
+use aoc_common::{certificate_field, certificate_indices, check_answer, json, read_parsed, Certificate, Certified, Error, Grid, InputSource, ParseError, Solution, Tile};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Square {
+    Open,
+    Tree,
+}
+
+impl Tile for Square {
+    const EXPECTED: &'static str = "`.` or `#`";
+
+    fn from_char(ch: char) -> Option<Square> {
+        match ch {
+            '.' => Some(Square::Open),
+            '#' => Some(Square::Tree),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Square::Open => '.',
+            Square::Tree => '#',
+        }
+    }
+}
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Grid<Square>, Error> {
+    return read_parsed(source, parse_map);
+}
+
+// Every row must be made of `.` and `#` and be as wide as the first.
+pub fn parse_map(contents: &str) -> Result<Grid<Square>, ParseError> {
+    return Grid::parse(contents);
+}
+
+pub const SLOPES: [(usize, usize); 5] = [
+    (1, 1),
+    (3, 1),
+    (5, 1),
+    (7, 1),
+    (1, 2)
+];
+
+/// The rows in which the slope from the top left meets a tree.
+pub fn tree_rows_on_slope(map: &Grid<Square>, dx: usize, dy: usize) -> Vec<usize> {
+
+    let mut tree_rows = Vec::new();
+    let mut x = 0;
+    let mut y = 0;
+    while y < map.height() {
+        if *map.get_wrapping(x as isize, y as isize) == Square::Tree {
+            tree_rows.push(y);
+        }
+
+        x = x + dx;
+        y = y + dy;
+    }
+
+    return tree_rows;
+}
+
+pub fn count_trees_on_slope(map: &Grid<Square>, dx: usize, dy: usize) -> u32 {
+    return tree_rows_on_slope(map, dx, dy).len() as u32;
+}
+
+fn certify_slopes(map: &Grid<Square>, slopes: &[(usize, usize)]) -> Certified<u32> {
+    let mut product = 1;
+    let mut certified = Vec::new();
+    for &(dx, dy) in slopes {
+        let tree_rows = tree_rows_on_slope(map, dx, dy);
+        product = product * tree_rows.len() as u32;
+        certified.push(json!({ "right": dx, "down": dy, "tree_rows": tree_rows }));
+    }
+    return Certified::new(product, json!({ "slopes": certified }));
+}
+
+/// Confirms that each slope meets a tree in exactly the rows listed for it.
+pub fn check_tree_rows(map: &Grid<Square>, slopes: &[(usize, usize)], certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let certified: Vec<Certificate> = certificate_field(certificate, "slopes")?;
+    if certified.len() != slopes.len() {
+        return Err(Error::Certificate(format!("{} slopes instead of {}", certified.len(), slopes.len())));
+    }
+    let mut product: u64 = 1;
+    for (slope, &(dx, dy)) in certified.iter().zip(slopes) {
+        if (certificate_field(slope, "right")?, certificate_field(slope, "down")?) != (dx, dy) {
+            return Err(Error::Certificate(format!("the slopes should be {:?}", slopes)));
+        }
+        let tree_rows = certificate_indices(slope, "tree_rows", map.height())?;
+        // The slope passes through row y at column y / dy * dx.
+        for y in (0..map.height()).step_by(dy) {
+            let is_tree = *map.get_wrapping((y / dy * dx) as isize, y as isize) == Square::Tree;
+            if is_tree != tree_rows.contains(&y) {
+                return Err(Error::Certificate(format!("right {} down {} {} a tree in row {}", dx, dy, if is_tree { "meets" } else { "misses" }, y)));
+            }
+        }
+        if tree_rows.iter().any(|y| y % dy != 0) {
+            return Err(Error::Certificate(format!("right {} down {} lists rows it never reaches", dx, dy)));
+        }
+        product = product * tree_rows.len() as u64;
+    }
+    check_answer(product, answer)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Grid<Square>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Grid<Square>, Error> {
+        Ok(parse_map(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(map: &Grid<Square>) -> Result<Certified<u32>, Error> {
+        Ok(certify_slopes(map, &[(3, 1)]))
+    }
+
+    fn part2(map: &Grid<Square>) -> Result<Certified<u32>, Error> {
+        Ok(certify_slopes(map, &SLOPES))
+    }
+
+    fn check(map: &Grid<Square>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_tree_rows(map, &[(3, 1)], certificate, answer),
+            _ => check_tree_rows(map, &SLOPES, certificate, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_tree_rows() {
+        let map = Day3::parse("..#.\n#..#\n.#..\n.#..\n").unwrap();
+        let solved = Day3::part1(&map).unwrap();
+        assert_eq!(solved.certificate, json!({ "slopes": [{ "right": 3, "down": 1, "tree_rows": [1, 3] }] }));
+        assert!(Day3::check(&map, 1, &solved.certificate, "2").is_ok());
+
+        let missed = json!({ "slopes": [{ "right": 3, "down": 1, "tree_rows": [1] }] });
+        assert_eq!(Day3::check(&map, 1, &missed, "1").unwrap_err().to_string(), "certificate rejected: right 3 down 1 meets a tree in row 3");
+        let wrong_slope = json!({ "slopes": [{ "right": 1, "down": 1, "tree_rows": [] }] });
+        assert_eq!(Day3::check(&map, 1, &wrong_slope, "0").unwrap_err().to_string(), "certificate rejected: the slopes should be [(3, 1)]");
+        let solved = Day3::part2(&map).unwrap();
+        assert!(Day3::check(&map, 2, &solved.certificate, &solved.answer.to_string()).is_ok());
+    }
+}
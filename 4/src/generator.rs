@@ -0,0 +1,89 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+fn digits(rng: &mut StdRng, n: usize) -> String {
+    return (0..n).map(|_| rng.gen_range(b'0'..=b'9') as char).collect();
+}
+
+fn year(rng: &mut StdRng, valid: bool, min: u32, max: u32) -> String {
+    if valid {
+        return rng.gen_range(min..=max).to_string();
+    }
+    let year = if rng.gen_bool(0.5) { rng.gen_range(1900..min) } else { rng.gen_range(max + 1..2040) };
+    return year.to_string();
+}
+
+// A value for `key` that does or doesn't pass its field's validation.
+fn field_value(rng: &mut StdRng, key: &str, valid: bool) -> String {
+    match key {
+        "byr" => year(rng, valid, 1920, 2002),
+        "iyr" => year(rng, valid, 2010, 2020),
+        "eyr" => year(rng, valid, 2020, 2030),
+        "hgt" => match (valid, rng.gen_bool(0.5)) {
+            (true, true) => format!("{}cm", rng.gen_range(150..=193)),
+            (true, false) => format!("{}in", rng.gen_range(59..=76)),
+            (false, true) => format!("{}cm", rng.gen_range(194..=250)),
+            (false, false) => rng.gen_range(59..=193).to_string(),
+        },
+        "hcl" => {
+            let hex: String = (0..6).map(|_| *b"0123456789abcdef".choose(rng).unwrap() as char).collect();
+            if valid { format!("#{}", hex) } else { hex }
+        },
+        "ecl" => {
+            if valid { EYE_COLORS.choose(rng).unwrap().to_string() } else { "xry".to_string() }
+        },
+        "pid" => {
+            let n = if valid { 9 } else { rng.gen_range(5..=8) };
+            digits(rng, n)
+        },
+        _ => rng.gen_range(100..=350).to_string(),
+    }
+}
+
+/// `size` passports separated by blank lines. Most fields are present and
+/// valid, so a good share of passports pass both parts' checks.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut passports: Vec<String> = Vec::new();
+    for _ in 0..size {
+        let mut fields: Vec<String> = Vec::new();
+        for key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+            if rng.gen_bool(0.95) {
+                let valid = rng.gen_bool(0.95);
+                fields.push(format!("{}:{}", key, field_value(&mut rng, key, valid)));
+            }
+        }
+        fields.shuffle(&mut rng);
+
+        // Spread the fields over a few lines.
+        let mut passport = String::new();
+        for (idx, fld) in fields.iter().enumerate() {
+            if idx > 0 {
+                passport.push(if rng.gen_bool(0.3) { '\n' } else { ' ' });
+            }
+            passport.push_str(fld);
+        }
+        passports.push(passport);
+    }
+    return passports.join("\n\n") + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count_complete_entries, count_valid_entries, group_entries};
+
+    #[test]
+    fn test_generate() {
+        let text = generate(100, 4);
+        let entries = group_entries(&text).unwrap();
+        assert_eq!(entries.len(), 100);
+
+        let complete = count_complete_entries(&entries);
+        let valid = count_valid_entries(&entries);
+        assert!(0 < valid && valid < complete && complete < 100);
+    }
+}
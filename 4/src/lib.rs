@@ -0,0 +1,241 @@
+pub mod generator;
+
+use log::debug;
+use std::collections::HashMap;
+
+use aoc_common::combinator::{alt, char, expect, key_value, literal, pair, parse_all, parse_line, preceded, take_while_m_n, tokens, unsigned};
+use aoc_common::{certificate_field, check_answer, json, read_parsed, split_sections, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<Passport>, Error> {
+    return read_parsed(source, group_entries);
+}
+
+pub fn parse_fields(ln: &str) -> Result<HashMap<&str, &str>, ParseError> {
+    let fields = parse_line(ln, tokens(expect("a field like `key:value`", key_value(':'))))?;
+    return Ok(fields.into_iter().collect());
+}
+
+pub fn print_hash_map(map: &HashMap<String, String>) {
+    for (k, v) in map.iter() {
+        println!("{}: {}", k, v);
+    }
+}
+
+fn validate_int_field(fld_val: &str, min: u32, max: u32) -> bool {
+    let num = fld_val.parse::<u32>();
+    match num {
+        Ok(x) => return (min <= x) && (x <= max),
+        Err(_) => false
+    }
+}
+
+fn validate_byr(fld_val: &str) -> bool {
+    return validate_int_field(fld_val, 1920, 2002);
+}
+
+fn validate_iyr(fld_val: &str) -> bool {
+    return validate_int_field(fld_val, 2010, 2020);
+}
+
+fn validate_eyr(fld_val: &str) -> bool {
+    return validate_int_field(fld_val, 2020, 2030);
+}
+
+fn validate_hgt(fld_val: &str) -> bool {
+    let (num_val, unit) = match parse_all(fld_val, pair(unsigned::<u32>(), alt((literal("cm"), literal("in"))))) {
+        Some(height) => height,
+        None => return false
+    };
+
+    if unit == "cm" {
+        return (150..=193).contains(&num_val);
+    } else if unit == "in" {
+        return (59..=76).contains(&num_val);
+    } else {
+        panic!()
+    }
+}
+
+fn validate_hcl(fld_val: &str) -> bool {
+    let digit = |c: char| c.is_ascii_digit() || ('a'..='f').contains(&c);
+    return parse_all(fld_val, preceded(char('#'), take_while_m_n(6, 6, digit))).is_some();
+}
+
+fn validate_ecl(fld_val: &str) -> bool {
+    return ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&fld_val);
+}
+
+fn validate_pid(fld_val: &str) -> bool {
+    return parse_all(fld_val, take_while_m_n(9, 9, |c: char| c.is_ascii_digit())).is_some();
+}
+
+pub type Passport = HashMap<String, String>;
+
+// Passports are separated by blank lines, and their fields may span several lines.
+pub fn group_entries(contents: &str) -> Result<Vec<Passport>, ParseError> {
+    let mut entries: Vec<Passport> = vec![];
+
+    for section in split_sections(contents) {
+        let mut accum: Passport = HashMap::new();
+        for (line_no, ln) in section.lines() {
+            let entry_fields = parse_fields(ln).map_err(|err| err.on_line(line_no))?;
+            for (k, v) in entry_fields.iter() {
+                accum.insert(k.to_string(), v.to_string());
+            }
+        }
+        entries.push(accum);
+    }
+
+    return Ok(entries);
+}
+
+pub type Validator = Box<dyn Fn(&str) -> bool>;
+
+pub fn required_fields() -> Vec<(&'static str, Validator)> {
+    let required_fields: Vec<(&str, Validator)> = vec![
+        ("byr", Box::new(&validate_byr)),
+        ("iyr", Box::new(&validate_iyr)),
+        ("eyr", Box::new(&validate_eyr)),
+        ("hgt", Box::new(&validate_hgt)),
+        ("hcl", Box::new(&validate_hcl)),
+        ("ecl", Box::new(&validate_ecl)),
+        ("pid", Box::new(&validate_pid)),
+    ];
+    return required_fields;
+}
+
+/// The first required field that rules `entry` out by being missing or, when
+/// `check_values` is set, by holding an invalid value.
+pub fn rejecting_field(entry: &Passport, required_fields: &Vec<(&'static str, Validator)>, check_values: bool) -> Option<&'static str> {
+    for (fld, validator) in required_fields.iter() {
+        match entry.get(*fld) {
+            None => return Some(fld),
+            Some(x) if check_values && !validator(x) => {
+                debug!("invalid {}: {}", fld, x);
+                return Some(fld);
+            },
+            Some(_) => {},
+        }
+    }
+    return None;
+}
+
+pub fn count_complete_entries(entries: &Vec<Passport>) -> usize {
+    let required_fields = required_fields();
+    return entries.iter()
+        .filter(|entry| rejecting_field(entry, &required_fields, false).is_none())
+        .count();
+}
+
+pub fn count_valid_entries(entries: &Vec<Passport>) -> usize {
+    let required_fields = required_fields();
+    return entries.iter()
+        .filter(|entry| rejecting_field(entry, &required_fields, true).is_none())
+        .count();
+}
+
+// Every passport gets a verdict: null when it counts, or the field that rules it out.
+fn certify_entries(entries: &Vec<Passport>, check_values: bool) -> Certified<usize> {
+    let required_fields = required_fields();
+    let rejected_by: Vec<Option<&str>> = entries.iter()
+        .map(|entry| rejecting_field(entry, &required_fields, check_values))
+        .collect();
+    let count = rejected_by.iter().filter(|field| field.is_none()).count();
+    return Certified::new(count, json!({ "rejected_by": rejected_by }));
+}
+
+/// Confirms each passport's verdict: a rejecting field really is missing or
+/// invalid, and a passport with no such field has all of them in order.
+pub fn check_rejections(entries: &Vec<Passport>, check_values: bool, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let rejected_by: Vec<Option<String>> = certificate_field(certificate, "rejected_by")?;
+    if rejected_by.len() != entries.len() {
+        return Err(Error::Certificate(format!("{} verdicts for {} passports", rejected_by.len(), entries.len())));
+    }
+    let required_fields = required_fields();
+    let in_order = |entry: &Passport, fld: &str, validator: &Validator| {
+        entry.get(fld).is_some_and(|x| !check_values || validator(x))
+    };
+    for (idx, (entry, verdict)) in entries.iter().zip(&rejected_by).enumerate() {
+        match verdict {
+            Some(fld) => {
+                let validator = match required_fields.iter().find(|(name, _)| name == fld) {
+                    Some((_, validator)) => validator,
+                    None => return Err(Error::Certificate(format!("`{}` is not a required field", fld))),
+                };
+                if in_order(entry, fld, validator) {
+                    return Err(Error::Certificate(format!("passport {} has a good `{}`", idx, fld)));
+                }
+            },
+            None => {
+                if let Some((fld, _)) = required_fields.iter().find(|(fld, validator)| !in_order(entry, fld, validator)) {
+                    return Err(Error::Certificate(format!("passport {} has no good `{}`", idx, fld)));
+                }
+            },
+        }
+    }
+    check_answer(rejected_by.iter().filter(|verdict| verdict.is_none()).count(), answer)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Vec<Passport>, Error> {
+        Ok(group_entries(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(entries: &Vec<Passport>) -> Result<Certified<usize>, Error> {
+        Ok(certify_entries(entries, false))
+    }
+
+    fn part2(entries: &Vec<Passport>) -> Result<Certified<usize>, Error> {
+        Ok(certify_entries(entries, true))
+    }
+
+    fn check(entries: &Vec<Passport>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_rejections(entries, part == 2, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators() {
+        assert!(validate_hgt("60in") && validate_hgt("190cm"));
+        assert!(!validate_hgt("190in") && !validate_hgt("190") && !validate_hgt("cm"));
+        assert!(validate_hcl("#123abc") && !validate_hcl("#123abz") && !validate_hcl("123abc"));
+        assert!(validate_ecl("brn") && !validate_ecl("wat"));
+        assert!(validate_pid("000000001") && !validate_pid("0123456789"));
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let fields = parse_fields("ecl:gry pid:860033327 ").unwrap();
+        assert_eq!(fields.get("pid"), Some(&"860033327"));
+        let err = parse_fields("ecl:gry :860033327").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (9, "a field like `key:value`"));
+    }
+
+    #[test]
+    fn test_check_rejections() {
+        let entries = Day4::parse("byr:1980 iyr:2012 eyr:2030 hgt:74in\nhcl:#623a2f ecl:grn pid:087499704\n\nbyr:1980 iyr:2012 eyr:2030 hgt:74\nhcl:#623a2f ecl:grn pid:087499704\n\nbyr:1980\n").unwrap();
+        let solved = Day4::part2(&entries).unwrap();
+        assert_eq!(solved.certificate, json!({ "rejected_by": [null, "hgt", "iyr"] }));
+        assert!(Day4::check(&entries, 2, &solved.certificate, "1").is_ok());
+        assert!(Day4::check(&entries, 1, &json!({ "rejected_by": [null, null, "pid"] }), "2").is_ok());
+
+        let err = Day4::check(&entries, 1, &solved.certificate, "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: passport 1 has a good `hgt`");
+        let err = Day4::check(&entries, 2, &json!({ "rejected_by": [null, null, "iyr"] }), "2").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: passport 1 has no good `hgt`");
+        let err = Day4::check(&entries, 2, &json!({ "rejected_by": [null, "cid", "iyr"] }), "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: `cid` is not a required field");
+    }
+}
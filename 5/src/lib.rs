@@ -0,0 +1,190 @@
+use aoc_common::{certificate_field, check_answer, json, parse_lines, read_parsed, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<SeatCoord>, Error> {
+    return read_parsed(source, |contents| parse_lines(contents, decode_seat));
+}
+
+pub struct SeatCoord {
+    pub src: String,
+    pub row: u8,
+    pub col: u8,
+}
+
+impl SeatCoord {
+    pub fn seat_id(&self) -> u32 {
+        (self.row as u32) * 8 + (self.col as u32)
+    }
+}
+
+pub fn seat_id(row: u8, col: u8) -> u32 {
+    (row as u32) * 8 + (col as u32)
+}
+
+// Seven `F`/`B` row bits followed by three `L`/`R` column bits.
+pub fn decode_seat(ln: &str) -> Result<SeatCoord, ParseError> {
+    let mut num: u16 = 0;
+    let mut n_bits = 0;
+    for (offset, ch) in ln.char_indices() {
+        num = num << 1;
+        let bit: u16 = match (n_bits < 7, ch) {
+            (true, 'B') => 1,
+            (true, 'F') => 0,
+            (false, 'R') => 1,
+            (false, 'L') => 0,
+            (true, _) => return Err(ParseError::at(ln, offset, "`F` or `B`")),
+            (false, _) if n_bits < 10 => return Err(ParseError::at(ln, offset, "`L` or `R`")),
+            (false, _) => return Err(ParseError::at(ln, offset, "end of line")),
+        };
+        num = num | bit;
+        n_bits = n_bits + 1;
+    }
+    if n_bits < 10 {
+        return Err(ParseError::at(ln, ln.len(), "10 seat characters"));
+    }
+    return Ok(SeatCoord{
+        src: ln.to_string(),
+        row: (num >> 3) as u8,
+        col: (num & 0x0007) as u8,
+    });
+}
+
+pub fn find_seat_gaps(coords: &Vec<SeatCoord>) -> Vec<(u32, u32)> {
+    let min_row = coords.iter().map(|coord| coord.row).min().unwrap();
+    let max_row = coords.iter().map(|coord| coord.row).max().unwrap();
+
+    let mut seat_ids: Vec<u32> = coords.iter()
+        .map(|coord| coord.seat_id())
+        .collect();
+
+    let min_valid_seat_id = seat_id(min_row, 0);
+    let max_valid_seat_id = seat_id(max_row, 7);
+    seat_ids.sort_unstable();
+    let valid_seat_ids: Vec<u32> = seat_ids.into_iter()
+        .filter(|x| x >= &min_valid_seat_id && x <= &max_valid_seat_id)
+        .collect();
+    let seat_id_deltas: Vec<(u32, u32)> =
+        valid_seat_ids.iter()
+        .zip(valid_seat_ids.iter().skip(1))
+        .filter(|(&x, &y)| (y - x) == 2)
+        .map(|(&x, &y)| (x, y))
+        .collect();
+
+    return seat_id_deltas;
+}
+
+// A pass read straight as a 10 bit number, which is the seat ID the long way round.
+fn pass_seat_id(coords: &Vec<SeatCoord>, idx: usize) -> Result<u32, Error> {
+    match coords.get(idx) {
+        Some(coord) => Ok(coord.src.chars().fold(0, |id, ch| id * 2 + (ch == 'B' || ch == 'R') as u32)),
+        None => Err(Error::Certificate(format!("there is no boarding pass {}", idx))),
+    }
+}
+
+/// Confirms that boarding pass `highest` has the answer as its seat ID and no pass has a higher one.
+pub fn check_highest(coords: &Vec<SeatCoord>, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let highest = pass_seat_id(coords, certificate_field(certificate, "highest")?)?;
+    for idx in 0..coords.len() {
+        if pass_seat_id(coords, idx)? > highest {
+            return Err(Error::Certificate(format!("boarding pass {} has a higher seat ID", idx)));
+        }
+    }
+    check_answer(highest, answer)
+}
+
+/// Confirms that boarding passes `before` and `after` sit either side of the
+/// answer's seat, and that no pass has that seat.
+pub fn check_vacant(coords: &Vec<SeatCoord>, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let before = pass_seat_id(coords, certificate_field(certificate, "before")?)?;
+    let after = pass_seat_id(coords, certificate_field(certificate, "after")?)?;
+    if after != before + 2 {
+        return Err(Error::Certificate(format!("seats {} and {} do not have one seat between them", before, after)));
+    }
+    for idx in 0..coords.len() {
+        if pass_seat_id(coords, idx)? == before + 1 {
+            return Err(Error::Certificate(format!("boarding pass {} has seat {}", idx, before + 1)));
+        }
+    }
+    check_answer(before + 1, answer)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<SeatCoord>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(text: &str) -> Result<Vec<SeatCoord>, Error> {
+        Ok(parse_lines(text, decode_seat)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(coords: &Vec<SeatCoord>) -> Result<Certified<u32>, Error> {
+        let (highest, seat_id) = coords.iter()
+            .map(|coord| coord.seat_id())
+            .enumerate()
+            .max_by_key(|(_, seat_id)| *seat_id)
+            .ok_or_else(|| Error::NoAnswer("no boarding passes".to_string()))?;
+        Ok(Certified::new(seat_id, json!({ "highest": highest })))
+    }
+
+    fn part2(coords: &Vec<SeatCoord>) -> Result<Certified<u32>, Error> {
+        if coords.is_empty() {
+            return Err(Error::NoAnswer("no boarding passes".to_string()));
+        }
+        match find_seat_gaps(coords).as_slice() {
+            [(x, y)] => {
+                let pass_with = |id: u32| coords.iter().position(|coord| coord.seat_id() == id);
+                Ok(Certified::new(x + 1, json!({ "before": pass_with(*x), "after": pass_with(*y) })))
+            },
+            gaps => Err(Error::NoAnswer(format!("expected exactly one vacant seat, found {}", gaps.len()))),
+        }
+    }
+
+    fn check(coords: &Vec<SeatCoord>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_highest(coords, certificate, answer),
+            _ => check_vacant(coords, certificate, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_example_correctly() {
+        let example = "FBFBBFFRLR";
+        let coord = decode_seat(example).unwrap();
+        assert_eq!(coord.src, example);
+        assert_eq!(coord.row, 44);
+        assert_eq!(coord.col, 5);
+        assert_eq!(coord.seat_id(), 357);
+    }
+
+    #[test]
+    fn rejects_trailing_space() {
+        let err = decode_seat("FBFBBFFRLR ").err().unwrap();
+        assert_eq!(err.column, 11);
+        assert_eq!(err.found, Some(" ".to_string()));
+    }
+
+    #[test]
+    fn test_check_seats() {
+        let coords = Day5::parse("FFFFFFFRRR\nFFFFFFBLLR\nFFFFFFBLLL\nFFFFFFFRRL\n").unwrap();
+        let highest = Day5::part1(&coords).unwrap();
+        assert_eq!((highest.answer, &highest.certificate), (9, &json!({ "highest": 1 })));
+        assert!(Day5::check(&coords, 1, &highest.certificate, "9").is_ok());
+        let err = Day5::check(&coords, 1, &json!({ "highest": 2 }), "8").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: boarding pass 1 has a higher seat ID");
+
+        let coords = Day5::parse("FFFFFFFRRR\nFFFFFFBLLR\nFFFFFFFRRL\n").unwrap();
+        let vacant = Day5::part2(&coords).unwrap();
+        assert_eq!(vacant.certificate, json!({ "before": 0, "after": 1 }));
+        assert!(Day5::check(&coords, 2, &vacant.certificate, "8").is_ok());
+        let err = Day5::check(&coords, 2, &json!({ "before": 2, "after": 0 }), "7").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: seats 6 and 7 do not have one seat between them");
+    }
+}
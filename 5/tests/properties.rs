@@ -0,0 +1,23 @@
+use aoc5::decode_seat;
+use proptest::prelude::*;
+
+fn encode_seat(row: u8, col: u8) -> String {
+    let row_chars = (0..7).rev().map(|bit| if (row >> bit) & 1 == 1 { 'B' } else { 'F' });
+    let col_chars = (0..3).rev().map(|bit| if (col >> bit) & 1 == 1 { 'R' } else { 'L' });
+    return row_chars.chain(col_chars).collect();
+}
+
+proptest! {
+    #[test]
+    fn decode_inverts_encode(row in 0_u8..128, col in 0_u8..8) {
+        let coord = decode_seat(&encode_seat(row, col)).unwrap();
+        prop_assert_eq!((coord.row, coord.col), (row, col));
+        prop_assert_eq!(coord.seat_id(), row as u32 * 8 + col as u32);
+    }
+
+    #[test]
+    fn encode_inverts_decode(seat in "[FB]{7}[LR]{3}") {
+        let coord = decode_seat(&seat).unwrap();
+        prop_assert_eq!(encode_seat(coord.row, coord.col), seat);
+    }
+}
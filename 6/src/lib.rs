@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use aoc_common::{certificate_field, check_answer, group_records, json, parse_lines, read_parsed, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<Vec<String>>, Error> {
+    return read_parsed(source, parse_groups);
+}
+
+// Each line is one person's answers, `a` through `z`. Blank lines separate groups.
+pub fn parse_answer_lines(contents: &str) -> Result<Vec<String>, ParseError> {
+    return parse_lines(contents, |ln| {
+        match ln.char_indices().find(|(_, ch)| !ch.is_ascii_lowercase()) {
+            Some((offset, _)) => Err(ParseError::at(ln, offset, "a question letter `a` to `z`")),
+            None => Ok(ln.to_string()),
+        }
+    });
+}
+
+pub fn parse_groups(contents: &str) -> Result<Vec<Vec<String>>, ParseError> {
+    parse_answer_lines(contents)?;
+    let groups = group_records(contents).into_iter()
+        .map(|group| group.into_iter().map(|ln| ln.to_string()).collect())
+        .collect();
+    return Ok(groups);
+}
+
+// How many people in the group answered each question.
+fn tally_answers(group: &Vec<String>) -> HashMap<char, u32> {
+    let mut accum: HashMap<char, u32> = HashMap::new();
+    for ln in group {
+        let mut rec_chars: Vec<char> = ln.chars().collect();
+        rec_chars.sort();
+        rec_chars.dedup();
+        for ch in rec_chars {
+            let cnt = accum.get(&ch).cloned().unwrap_or(0);
+            accum.insert(ch, cnt + 1);
+        }
+    }
+    return accum;
+}
+
+/// The questions anyone in the group answered, in order.
+pub fn questions_for_any(group: &Vec<String>) -> String {
+    let mut questions: Vec<char> = tally_answers(group).into_keys().collect();
+    questions.sort();
+    return questions.into_iter().collect();
+}
+
+/// The questions everyone in the group answered, in order.
+pub fn questions_for_all(group: &Vec<String>) -> String {
+    let mut questions: Vec<char> = tally_answers(group).into_iter()
+        .filter(|(_, cnt)| *cnt as usize == group.len())
+        .map(|(ch, _)| ch)
+        .collect();
+    questions.sort();
+    return questions.into_iter().collect();
+}
+
+pub fn count_answers_for_any(groups: &Vec<Vec<String>>) -> u32 {
+    return groups.iter().map(|grp| questions_for_any(grp).len() as u32).sum();
+}
+
+pub fn count_answers_for_group(group: &Vec<String>) -> usize {
+    return questions_for_all(group).len();
+}
+
+pub fn count_answers_for_all(groups: &Vec<Vec<String>>) -> usize {
+    return groups.iter().map(count_answers_for_group).sum();
+}
+
+fn certify_questions<F: Fn(&Vec<String>) -> String>(groups: &Vec<Vec<String>>, questions_for: F) -> (usize, Certificate) {
+    let questions: Vec<String> = groups.iter().map(questions_for).collect();
+    let count = questions.iter().map(|q| q.len()).sum();
+    return (count, json!({ "questions": questions }));
+}
+
+/// Confirms that each group's listed questions are exactly those answered by
+/// anyone in it or, when `by_all` is set, by everyone in it.
+pub fn check_questions(groups: &Vec<Vec<String>>, by_all: bool, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let questions: Vec<String> = certificate_field(certificate, "questions")?;
+    if questions.len() != groups.len() {
+        return Err(Error::Certificate(format!("questions for {} groups, not {}", questions.len(), groups.len())));
+    }
+    for (idx, (group, listed)) in groups.iter().zip(&questions).enumerate() {
+        if listed.chars().any(|ch| !ch.is_ascii_lowercase() || listed.matches(ch).count() > 1) {
+            return Err(Error::Certificate(format!("group {} lists `{}`", idx, listed)));
+        }
+        for question in 'a'..='z' {
+            let answered_by = group.iter().filter(|person| person.contains(question)).count();
+            let counts = if by_all { answered_by == group.len() } else { answered_by > 0 };
+            if counts != listed.contains(question) {
+                return Err(Error::Certificate(format!("group {} {} question `{}`", idx, if counts { "leaves out" } else { "should not list" }, question)));
+            }
+        }
+    }
+    check_answer(questions.iter().map(|q| q.len()).sum::<usize>(), answer)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Vec<String>>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(text: &str) -> Result<Vec<Vec<String>>, Error> {
+        Ok(parse_groups(text)?)
+    }
+
+    const VERSION: u32 = 2;
+
+    fn part1(groups: &Vec<Vec<String>>) -> Result<Certified<u32>, Error> {
+        let (count, certificate) = certify_questions(groups, questions_for_any);
+        Ok(Certified::new(count as u32, certificate))
+    }
+
+    fn part2(groups: &Vec<Vec<String>>) -> Result<Certified<usize>, Error> {
+        let (count, certificate) = certify_questions(groups, questions_for_all);
+        Ok(Certified::new(count, certificate))
+    }
+
+    fn check(groups: &Vec<Vec<String>>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        check_questions(groups, part == 2, certificate, answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countd_all_in_group_correctly() {
+        let example: Vec<&str> = vec![
+            "ab", "ac"
+        ];
+        let cnt = count_answers_for_group(&example.iter().map(|x| x.to_string()).collect());
+        assert_eq!(cnt, 1);
+    }
+
+    #[test]
+    fn test_check_questions() {
+        let groups = Day6::parse("ab\nac\n\nb\n").unwrap();
+        let solved = Day6::part1(&groups).unwrap();
+        assert_eq!((solved.answer, &solved.certificate), (4, &json!({ "questions": ["abc", "b"] })));
+        assert!(Day6::check(&groups, 1, &solved.certificate, "4").is_ok());
+        assert!(Day6::check(&groups, 2, &json!({ "questions": ["a", "b"] }), "2").is_ok());
+
+        let err = Day6::check(&groups, 2, &solved.certificate, "4").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: group 0 should not list question `b`");
+        let err = Day6::check(&groups, 1, &json!({ "questions": ["abc", ""] }), "3").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: group 1 leaves out question `b`");
+        let err = Day6::check(&groups, 1, &json!({ "questions": ["abca", "b"] }), "5").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: group 0 lists `abca`");
+    }
+}
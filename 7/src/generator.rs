@@ -0,0 +1,92 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+const ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light",
+    "mirrored", "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const COLORS: [&str; 33] = [
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson",
+    "cyan", "fuchsia", "gold", "gray", "green", "indigo", "lavender", "lime", "magenta",
+    "maroon", "olive", "orange", "plum", "purple", "red", "salmon", "silver", "tan", "teal",
+    "tomato", "turquoise", "violet", "white", "yellow",
+];
+
+// Bags only contain bags from deeper levels, which keeps the rules acyclic and
+// the number of bags inside any one bag within a u32.
+const LEVELS: u32 = 6;
+
+// `n` distinct colors, starting with shiny gold.
+fn bag_colors(rng: &mut StdRng, n: usize) -> Vec<String> {
+    let mut colors: Vec<String> = vec!["shiny gold".to_string()];
+    let mut round = 0;
+    while colors.len() < n {
+        // Once every adjective and color pairing is used, number the colors to keep them distinct.
+        let suffix = if round == 0 { "".to_string() } else { round.to_string() };
+        let mut pairings: Vec<String> = ADJECTIVES.iter()
+            .flat_map(|adj| COLORS.iter().map(move |color| format!("{} {}", adj, color)))
+            .map(|name| name + &suffix)
+            .filter(|name| name != "shiny gold")
+            .collect();
+        pairings.shuffle(rng);
+        colors.extend(pairings.into_iter().take(n - colors.len()));
+        round = round + 1;
+    }
+    return colors;
+}
+
+/// Acyclic rules for `size` bag colors, at least 2, including shiny gold.
+/// Some bag always contains shiny gold. Only bags on the deepest level, or with
+/// nothing deeper to hold, contain no other bags.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let colors = bag_colors(&mut rng, size.max(2));
+    let mut levels: Vec<u32> = colors.iter().map(|_| rng.gen_range(0..=LEVELS)).collect();
+    levels[0] = LEVELS / 2;
+    levels[1] = 0;
+
+    let mut lines: Vec<String> = Vec::new();
+    for (idx, color) in colors.iter().enumerate() {
+        let deeper: Vec<usize> = (0..colors.len()).filter(|&i| levels[i] > levels[idx]).collect();
+        let n_contents = rng.gen_range(1..=4);
+        let mut contents: Vec<usize> = deeper.choose_multiple(&mut rng, n_contents).cloned().collect();
+        if idx == 1 && !contents.contains(&0) {
+            contents.push(0);
+        }
+
+        let described: Vec<String> = contents.iter().map(|&i| {
+            let cnt = rng.gen_range(1..=5);
+            format!("{} {} {}", cnt, colors[i], if cnt == 1 { "bag" } else { "bags" })
+        }).collect();
+        if described.is_empty() {
+            lines.push(format!("{} bags contain no other bags.", color));
+        } else {
+            lines.push(format!("{} bags contain {}.", color, described.join(", ")));
+        }
+    }
+    lines.shuffle(&mut rng);
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::to_lines;
+    use crate::{count_bags_containing, n_bags_in_given_bag, parse_bag_rules};
+
+    #[test]
+    fn test_generate() {
+        let lines = to_lines(&generate(300, 3));
+        let rules = parse_bag_rules(&lines).unwrap();
+        assert_eq!(rules.len(), 300);
+
+        let shiny_gold = "shiny gold".to_string();
+        assert!(count_bags_containing(&rules, &shiny_gold) > 0);
+        n_bags_in_given_bag(&rules, &shiny_gold).unwrap();
+        for color in rules.keys() {
+            n_bags_in_given_bag(&rules, color).unwrap();
+        }
+    }
+}
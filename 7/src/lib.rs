@@ -0,0 +1,323 @@
+pub mod generator;
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use aoc_common::combinator::{alpha1, alt, char, cut, expect, literal, opt, parse_line, recognize, separated_list1, separated_pair, terminated, tuple, unsigned, value, PResult};
+use aoc_common::{certificate_field, check_answer, checked_add, checked_mul, json, read_lines, to_lines, Certificate, Certified, Error, InputSource, Params, ParseError, Solution};
+
+#[cfg(not(feature = "bigint"))]
+pub type Count = u32;
+#[cfg(feature = "bigint")]
+pub type Count = aoc_common::BigUint;
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    return read_lines(source);
+}
+
+// Colors are two words, like `shiny gold`.
+fn bag_color(input: &str) -> PResult<'_, &str> {
+    return expect("a bag color", recognize(separated_pair(alpha1, char(' '), alpha1)))(input);
+}
+
+fn bag_count(input: &str) -> PResult<'_, (u32, &str)> {
+    let bags = alt((literal(" bags"), literal(" bag")));
+    return separated_pair(expect("a bag count", unsigned()), char(' '), terminated(bag_color, bags))(input);
+}
+
+pub fn parse_single_bag_rule(ln: String) -> Result<(String, Vec<(u32, String)>), ParseError> {
+    // Once a `, ` is seen another bag must follow, so a bad one is reported as itself.
+    let contents = alt((value(vec![], literal("no other bags")), separated_list1(literal(", "), cut(bag_count))));
+    let (color, _, contents, _) = parse_line(&ln, tuple((
+        bag_color,
+        literal(" bags contain "),
+        expect("a bag count or `no other bags`", contents),
+        opt(char('.')),
+    )))?;
+
+    let valid_contents = contents.into_iter().map(|(cnt, color)| (cnt, color.to_string())).collect();
+    return Ok((color.to_string(), valid_contents));
+}
+
+pub fn parse_bag_rules(lines: &Vec<String>) -> Result<HashMap<String, Vec<(u32, String)>>, ParseError> {
+    let mut validity_mapping: HashMap<String, Vec<(u32, String)>> = HashMap::new();
+    for (idx, ln) in lines.iter().enumerate() {
+        let (color, valid_contents) = parse_single_bag_rule(ln.clone()).map_err(|err| err.on_line(idx + 1))?;
+        validity_mapping.insert(color, valid_contents);
+    }
+    return Ok(validity_mapping);
+}
+
+/// Every color that can end up holding a `search_color` bag, each paired with
+/// a color it holds directly that is either `search_color` or listed before it.
+pub fn find_holders(rules: &HashMap<String, Vec<(u32, String)>>, search_color: &String) -> Vec<(String, String)> {
+    let mut colors: Vec<&String> = rules.keys().collect();
+    colors.sort();
+
+    let mut holders: Vec<(String, String)> = Vec::new();
+    let mut found: HashSet<&String> = HashSet::new();
+    let mut queue: VecDeque<&String> = VecDeque::from([search_color]);
+    while let Some(inner) = queue.pop_front() {
+        for color in colors.iter() {
+            if !found.contains(color) && rules[*color].iter().any(|(_, held)| held == inner) {
+                found.insert(color);
+                holders.push((color.to_string(), inner.clone()));
+                queue.push_back(color);
+            }
+        }
+    }
+    return holders;
+}
+
+pub fn count_bags_containing(rules: &HashMap<String, Vec<(u32, String)>>, search_color: &String) -> u32 {
+    return find_holders(rules, search_color).len() as u32;
+}
+
+// Records in `inside` the count for `candidate_color` and every color inside it,
+// which doubles as the certificate.
+fn count_bags_inside(rules: &HashMap<String, Vec<(u32, String)>>, candidate_color: &String, inside: &mut BTreeMap<String, Count>) -> Result<(), Error> {
+    return count_bags_inside_rec(rules, candidate_color, &mut HashSet::new(), inside);
+}
+
+// `counting` holds the colors whose counts are still being worked out, so
+// meeting one of them again means the rules have a bag inside itself.
+fn count_bags_inside_rec(rules: &HashMap<String, Vec<(u32, String)>>, candidate_color: &String, counting: &mut HashSet<String>, inside: &mut BTreeMap<String, Count>) -> Result<(), Error> {
+    if inside.contains_key(candidate_color) {
+        return Ok(());
+    }
+    if !counting.insert(candidate_color.clone()) {
+        return Err(Error::NoAnswer(format!("{} bags end up inside themselves", candidate_color)));
+    }
+    let what = format!("the number of bags inside {}", candidate_color);
+    let mut sum = Count::from(0_u32);
+
+    for (cnt, color) in rules.get(candidate_color).into_iter().flatten() {
+        let cnt = Count::from(*cnt);
+        count_bags_inside_rec(rules, color, counting, inside)?;
+        let inner = checked_mul(&cnt, &inside[color], &what)?;
+        sum = checked_add(&checked_add(&sum, &cnt, &what)?, &inner, &what)?;
+    }
+
+    counting.remove(candidate_color);
+    inside.insert(candidate_color.clone(), sum);
+    return Ok(());
+}
+
+pub fn n_bags_in_given_bag(rules: &HashMap<String, Vec<(u32, String)>>, candidate_color: &String) -> Result<Count, Error> {
+    let mut inside = BTreeMap::new();
+    count_bags_inside(rules, candidate_color, &mut inside)?;
+    return Ok(inside.remove(candidate_color).expect("Counted color."));
+}
+
+/// Confirms that each listed holder directly holds the color it is paired
+/// with, and that no unlisted color holds `search_color` or a listed one.
+pub fn check_holders(rules: &HashMap<String, Vec<(u32, String)>>, search_color: &String, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let holders: Vec<(String, String)> = certificate_field(certificate, "holders")?;
+    let mut listed: HashSet<&String> = HashSet::new();
+    for (holder, via) in &holders {
+        if via != search_color && !listed.contains(via) {
+            return Err(Error::Certificate(format!("{} is listed before it is known to hold {}", via, search_color)));
+        }
+        if !rules.get(holder).is_some_and(|contents| contents.iter().any(|(_, held)| held == via)) {
+            return Err(Error::Certificate(format!("{} bags do not hold {} bags", holder, via)));
+        }
+        if !listed.insert(holder) {
+            return Err(Error::Certificate(format!("{} is listed twice", holder)));
+        }
+    }
+    for (color, contents) in rules {
+        if listed.contains(color) {
+            continue;
+        }
+        if let Some((_, held)) = contents.iter().find(|(_, held)| held == search_color || listed.contains(held)) {
+            return Err(Error::Certificate(format!("{} bags hold {} bags but are not listed", color, held)));
+        }
+    }
+    check_answer(holders.len(), answer)
+}
+
+/// Confirms that the count of bags inside each listed color adds up from the
+/// counts for the colors its rule holds.
+pub fn check_bags_inside(rules: &HashMap<String, Vec<(u32, String)>>, candidate_color: &String, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    // Counts are strings, as they can be too big for JSON numbers.
+    let inside: BTreeMap<String, String> = certificate_field(certificate, "inside")?;
+    let count_for = |color: &String| -> Result<Count, Error> {
+        match inside.get(color) {
+            Some(count) => count.parse().map_err(|_| Error::Certificate(format!("`{}` is not a count of bags", count))),
+            None => Err(Error::Certificate(format!("the count for {} is missing", color))),
+        }
+    };
+    for color in inside.keys() {
+        let what = format!("the number of bags inside {}", color);
+        let mut sum = Count::from(0_u32);
+        for (cnt, held) in rules.get(color).into_iter().flatten() {
+            let with_inner = checked_add(&Count::from(1_u32), &count_for(held)?, &what)?;
+            sum = checked_add(&sum, &checked_mul(&Count::from(*cnt), &with_inner, &what)?, &what)?;
+        }
+        if sum != count_for(color)? {
+            return Err(Error::Certificate(format!("{} bags hold {} bags, not {}", color, sum, inside[color])));
+        }
+    }
+    check_answer(count_for(candidate_color)?, answer)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = HashMap<String, Vec<(u32, String)>>;
+    type Answer1 = u32;
+    type Answer2 = Count;
+
+    fn parse(text: &str) -> Result<HashMap<String, Vec<(u32, String)>>, Error> {
+        let lines = to_lines(text);
+        Ok(parse_bag_rules(&lines)?)
+    }
+
+    const PARAMS: &'static [&'static str] = &["color"];
+
+    const VERSION: u32 = 2;
+
+    fn part1(rules: &HashMap<String, Vec<(u32, String)>>) -> Result<Certified<u32>, Error> {
+        Day7::part1_with(rules, &Params::new())
+    }
+
+    fn part2(rules: &HashMap<String, Vec<(u32, String)>>) -> Result<Certified<Count>, Error> {
+        Day7::part2_with(rules, &Params::new())
+    }
+
+    fn part1_with(rules: &HashMap<String, Vec<(u32, String)>>, params: &Params) -> Result<Certified<u32>, Error> {
+        let color: String = params.get_or("color", "shiny gold".to_string())?;
+        let holders = find_holders(rules, &color);
+        Ok(Certified::new(holders.len() as u32, json!({ "holders": holders })))
+    }
+
+    fn part2_with(rules: &HashMap<String, Vec<(u32, String)>>, params: &Params) -> Result<Certified<Count>, Error> {
+        let color: String = params.get_or("color", "shiny gold".to_string())?;
+        let mut inside = BTreeMap::new();
+        count_bags_inside(rules, &color, &mut inside)?;
+        let counts: BTreeMap<&String, String> = inside.iter().map(|(color, count)| (color, count.to_string())).collect();
+        let certificate = json!({ "inside": counts });
+        Ok(Certified::new(inside.remove(&color).expect("Counted color."), certificate))
+    }
+
+    fn check(rules: &HashMap<String, Vec<(u32, String)>>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        Day7::check_with(rules, part, certificate, answer, &Params::new())
+    }
+
+    fn check_with(rules: &HashMap<String, Vec<(u32, String)>>, part: u8, certificate: &Certificate, answer: &str, params: &Params) -> Result<(), Error> {
+        let color: String = params.get_or("color", "shiny gold".to_string())?;
+        match part {
+            1 => check_holders(rules, &color, certificate, answer),
+            _ => check_bags_inside(rules, &color, certificate, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_rule_parse_test_no_others() {
+        let (color, valid_contents) = parse_single_bag_rule("faded blue bags contain no other bags".to_string()).unwrap();
+        assert_eq!(color, "faded blue");
+        assert_eq!(valid_contents.len(), 0);
+    }
+
+    #[test]
+    fn single_rule_parse_test_single_bag_contents() {
+        let (color, valid_contents) = parse_single_bag_rule("bright white bags contain 1 shiny gold bags".to_string()).unwrap();
+        assert_eq!(color, "bright white");
+        assert_eq!(valid_contents.len(), 1);
+    }
+
+    #[test]
+    fn single_rule_parse_test_multiple_bag_contents() {
+        let (color, valid_contents) = parse_single_bag_rule("dark orange bags contain 3 bright white bags, 4 muted yellow bags.".to_string()).unwrap();
+        assert_eq!(color, "dark orange");
+        assert_eq!(valid_contents.len(), 2);
+        assert_eq!(valid_contents[0].1, "bright white".to_string());
+        assert_eq!(valid_contents[1].1, "muted yellow".to_string());
+    }
+
+    #[test]
+    fn single_rule_parse_test_errors() {
+        let err = parse_single_bag_rule("dark orange bags contain 3 bright white bags, muted yellow bags.".to_string()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (47, "a bag count"));
+        let err = parse_single_bag_rule("dark orange bags contain bright white bags.".to_string()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (26, "a bag count or `no other bags`"));
+        let err = parse_single_bag_rule("3 dark orange bags contain no other bags.".to_string()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "a bag color"));
+    }
+
+    #[test]
+    fn accum_path_stats_test_example() {
+        let rules = parse_bag_rules(&slurp_input("example").unwrap()).unwrap();
+        let accum = n_bags_in_given_bag(&rules, &("shiny gold".to_string())).unwrap();
+        assert_eq!(Count::from(126_u32), accum);
+    }
+
+    #[test]
+    fn accum_path_stats_test_example1() {
+        let rules = parse_bag_rules(&slurp_input("example1").unwrap()).unwrap();
+        let accum = n_bags_in_given_bag(&rules, &("light red".to_string())).unwrap();
+        assert_eq!(Count::from(26_u32), accum);
+    }
+
+    #[test]
+    fn test_check_holders() {
+        let rules = parse_bag_rules(&slurp_input("example1").unwrap()).unwrap();
+        let solved = Day7::part1(&rules).unwrap();
+        assert_eq!(solved.certificate["holders"][0], json!(["bright white", "shiny gold"]));
+        assert!(Day7::check(&rules, 1, &solved.certificate, "3").is_ok());
+
+        let mut skipped = solved.certificate.clone();
+        skipped["holders"].as_array_mut().unwrap().pop();
+        let err = Day7::check(&rules, 1, &skipped, "2").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: light red bags hold bright white bags but are not listed");
+        let err = Day7::check(&rules, 1, &json!({ "holders": [["light red", "bright white"]] }), "1").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: bright white is listed before it is known to hold shiny gold");
+    }
+
+    #[test]
+    fn test_check_bags_inside() {
+        let rules = parse_bag_rules(&slurp_input("example").unwrap()).unwrap();
+        let solved = Day7::part2(&rules).unwrap();
+        assert_eq!(solved.certificate["inside"]["shiny gold"], json!("126"));
+        assert!(Day7::check(&rules, 2, &solved.certificate, "126").is_ok());
+
+        let mut inflated = solved.certificate.clone();
+        inflated["inside"]["dark violet"] = json!("1");
+        let err = Day7::check(&rules, 2, &inflated, "126").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: dark blue bags hold 4 bags, not 2");
+    }
+
+    #[test]
+    fn accum_path_stats_test_overflow() {
+        let lines: Vec<String> = vec![
+            "dark red bags contain 1000 dark orange bags.",
+            "dark orange bags contain 1000 dark yellow bags.",
+            "dark yellow bags contain 1000 dark green bags.",
+            "dark green bags contain 1000 dark blue bags.",
+            "dark blue bags contain no other bags.",
+        ].into_iter().map(|x| x.to_string()).collect();
+        let rules = parse_bag_rules(&lines).unwrap();
+        let accum = n_bags_in_given_bag(&rules, &("dark red".to_string()));
+        #[cfg(not(feature = "bigint"))]
+        assert!(matches!(accum, Err(Error::Overflow(_))));
+        #[cfg(feature = "bigint")]
+        assert_eq!(accum.unwrap(), Count::from(1_001_001_001_000_u64));
+    }
+
+    #[test]
+    fn accum_path_stats_test_cycle() {
+        let lines: Vec<String> = vec![
+            "shiny gold bags contain 1 dark red bag.",
+            "dark red bags contain 2 dark orange bags.",
+            "dark orange bags contain 1 shiny gold bag.",
+        ].into_iter().map(|x| x.to_string()).collect();
+        let rules = parse_bag_rules(&lines).unwrap();
+        let err = n_bags_in_given_bag(&rules, &("shiny gold".to_string())).unwrap_err();
+        assert_eq!(err.to_string(), "no answer: shiny gold bags end up inside themselves");
+    }
+
+}
@@ -0,0 +1,63 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// A program of `size` instructions, at least 1, that loops forever but
+/// terminates when exactly one `jmp` is switched to a `nop`.
+///
+/// Execution runs through the instructions up to a `jmp` that goes back, and
+/// that `jmp` is the one to fix. Before it, `jmp`s only go forward no further
+/// than it and `nop`s would only jump back, so no other switch escapes the loop.
+/// After it, every `jmp` goes forward towards the end of the program.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = size.max(1) as i32;
+    let loop_end = rng.gen_range(0..size);
+
+    let mut text = String::new();
+    for idx in 0..size {
+        let (op, operand) = if idx == loop_end {
+            ("jmp", -rng.gen_range(0..=idx))
+        } else {
+            // How far a forward jump may go: to the looping jmp, or to the end of the program.
+            let limit = if idx < loop_end { loop_end - idx } else { size - idx };
+            match rng.gen_range(0..3) {
+                0 => ("acc", rng.gen_range(-50..=50)),
+                1 => ("jmp", rng.gen_range(1..=limit.min(10))),
+                _ if idx < loop_end => ("nop", -rng.gen_range(0..=idx)),
+                _ => ("nop", rng.gen_range(-100..=100)),
+            }
+        };
+        text.push_str(&format!("{} {:+}\n", op, operand));
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::to_lines;
+    use crate::{parse_program, run_program};
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let lines = to_lines(&generate(200, seed));
+            let prog = parse_program(&lines).unwrap();
+            assert!(!run_program(&mut prog.to_vec(), 1).unwrap().0);
+
+            let mut fixes = 0;
+            for idx in 0..prog.len() {
+                let mut prog1 = prog.to_vec();
+                match prog1[idx].op.as_str() {
+                    "jmp" => prog1[idx].switch_to("nop"),
+                    "nop" => prog1[idx].switch_to("jmp"),
+                    _ => continue,
+                }
+                if let Ok((true, _)) = run_program(&mut prog1, 1) {
+                    fixes = fixes + 1;
+                }
+            }
+            assert_eq!(fixes, 1, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,317 @@
+pub mod generator;
+
+use log::{debug, trace};
+
+use aoc_common::{certificate_field, check_answer, checked_add, count, json, parse_integer, read_lines, to_lines, Certificate, Certified, Error, InputSource, ParseError, Solution};
+
+pub struct Instr {
+    pub op: String,
+    pub operand: i32,
+    pub exec_count: u32,
+}
+
+impl Clone for Instr {
+    fn clone(&self) -> Self {
+        Instr{
+            op: String::from(&self.op),
+            operand: self.operand,
+            exec_count: self.exec_count,
+        }
+    }    
+}
+
+impl Instr {
+    pub fn reset(&mut self) {
+        self.exec_count = 0;
+    }
+
+    pub fn switch_to(&mut self, op: &str) {
+        self.op = String::from(op);
+    }
+}
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    return read_lines(source);
+}
+
+pub fn parse_instruction(ln: &String) -> Result<Instr, ParseError> {
+    let pair: Vec<&str> = ln.splitn(2, " ").collect();
+    if !["acc", "jmp", "nop"].contains(&pair[0]) {
+        return Err(ParseError::at_token(ln, pair[0], "`acc`, `jmp` or `nop`"));
+    }
+    let operand_str = pair.get(1).cloned().unwrap_or(&ln[ln.len()..]);
+    let operand: i32 = parse_integer(ln, operand_str)?;
+    let instr = Instr{
+        op: String::from(pair[0]),
+        operand: operand,
+        exec_count: 0,
+    };
+    // let instr = match pair[0] {
+    //     "nop" => Instr{op: String::from("nop"), operand: pair[, exec_count: 0},
+    //     "acc" => Instr{op: String::from("acc"), operand: pair[1].parse::<i32>().unwrap(), exec_count: 0},
+    //     "jmp" => Instr{op: String::from("jmp"), operand: pair[1].parse::<i32>().unwrap(), exec_count: 0},
+    //     &_ => panic!()
+    // };
+    return Ok(instr);
+}
+
+pub fn parse_program(lines: &Vec<String>) -> Result<Vec<Instr>, ParseError> {
+    lines.iter()
+        .enumerate()
+        .map(|(idx, ln)| parse_instruction(ln).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+pub fn run_program(prog: &mut Vec<Instr>, exec_limit: u32) -> Result<(bool, i32), Error> {
+    return run_program_traced(prog, exec_limit, &mut Vec::new());
+}
+
+/// Runs the program like `run_program`, recording the address of each instruction executed.
+/// Jumping anywhere outside the program but just past its end is a fault.
+pub fn run_program_traced(prog: &mut Vec<Instr>, exec_limit: u32, executed: &mut Vec<usize>) -> Result<(bool, i32), Error> {
+    let mut ip: usize = 0;
+    let mut accum: i32 = 0;
+    let mut fault_flag: bool = false;
+    let mut steps: u64 = 0;
+    loop {
+        if ip == prog.len() {
+            break;
+        }
+
+        let instr = prog[ip].clone();
+        if instr.exec_count >= exec_limit {
+            fault_flag = true;
+            break;
+        }
+
+        prog[ip] = Instr{
+            op: String::from(&instr.op),
+            operand: instr.operand,
+            exec_count: instr.exec_count + 1
+        };
+        trace!("will exec {} {} at {}", instr.op, instr.operand, ip);
+        steps = steps + 1;
+        executed.push(ip);
+        let next: i64 = match instr.op.as_str() {
+            "acc" => {
+                accum = checked_add(&accum, &instr.operand, "the accumulator")?;
+                ip as i64 + 1
+            },
+            "jmp" => ip as i64 + instr.operand as i64,
+            "nop" => ip as i64 + 1,
+            op => return Err(Error::NoAnswer(format!("instruction {} is `{}`, not `acc`, `jmp` or `nop`", ip, op))),
+        };
+        if next < 0 || next > prog.len() as i64 {
+            return Err(Error::NoAnswer(format!("instruction {} jumps to {}, outside the program", ip, next)));
+        }
+        ip = next as usize;
+    }
+
+    count("vm_steps", steps);
+    return Ok((!fault_flag, accum));
+}
+
+pub fn reset_program(prog: &mut Vec<Instr>) {
+    for x in prog {
+        x.reset();
+    }
+}
+
+pub fn fix_program(prog: Vec<Instr>) -> Result<(bool, Vec<Instr>), Error> {
+    for offset in 0..prog.len() {
+        let mut prog1: Vec<Instr> = prog.to_vec();
+        let mut instr = prog1[offset].clone();
+        if instr.op == "acc" {
+            debug!("ignoring instr {} as it is acc", offset);
+            continue;
+        } else if instr.op == "jmp" {
+            instr.switch_to("nop");
+            prog1[offset] = instr.clone();
+            debug!("switched instr {} from jmp to {}", offset, &instr.op);
+        } else if instr.op == "nop" {
+            instr.switch_to("jmp");
+            prog1[offset] = instr.clone();
+            debug!("switched instr {} from nop to {}", offset, &instr.op);
+        }
+
+        match run_program(&mut prog1, 1) {
+            Ok((true, _)) => {
+                reset_program(&mut prog1);
+                return Ok((true, prog1));
+            },
+            Ok((false, _)) => {},
+            // A flip that jumps out of the program breaks it rather than fixing it.
+            Err(Error::NoAnswer(msg)) => debug!("flipping instr {} faults: {}", offset, msg),
+            Err(err) => return Err(err),
+        }
+    }
+    return Ok((false, vec![]));
+}
+
+// The instructions executed before the first repeat must each lead to the
+// next, the last must lead back to one of them, and their `acc`s must add up to `answer`.
+pub fn check_loop(prog: &Vec<Instr>, executed: &Vec<usize>, answer: &str) -> Result<(), Error> {
+    let next = |ip: usize| -> Result<i64, Error> {
+        match prog.get(ip) {
+            Some(instr) if instr.op == "jmp" => Ok(ip as i64 + instr.operand as i64),
+            Some(_) => Ok(ip as i64 + 1),
+            None => Err(Error::Certificate(format!("the program has no instruction {}", ip))),
+        }
+    };
+    let mut seen = vec![false; prog.len()];
+    let mut accum: i64 = 0;
+    let mut expected: i64 = 0;
+    for &ip in executed {
+        if ip as i64 != expected {
+            return Err(Error::Certificate(format!("instruction {} runs where {} should", ip, expected)));
+        }
+        match seen.get(ip) {
+            Some(true) => return Err(Error::Certificate(format!("instruction {} repeats before the end", ip))),
+            Some(false) => {},
+            None => return Err(Error::Certificate(format!("the program has no instruction {}", ip))),
+        }
+        seen[ip] = true;
+        if prog[ip].op == "acc" {
+            accum = accum + prog[ip].operand as i64;
+        }
+        expected = next(ip)?;
+    }
+    if expected < 0 || !seen.get(expected as usize).cloned().unwrap_or(false) {
+        return Err(Error::Certificate(format!("the program goes on to instruction {}, which has not run", expected)));
+    }
+    check_answer(accum, answer)
+}
+
+// Flipping the `jmp` or `nop` at `flipped` must let the program finish with `answer` in the accumulator.
+pub fn check_flip(prog: &Vec<Instr>, flipped: usize, answer: &str) -> Result<(), Error> {
+    let mut patched = prog.to_vec();
+    match patched.get(flipped).map(|instr| instr.op.as_str()) {
+        Some("jmp") => patched[flipped].switch_to("nop"),
+        Some("nop") => patched[flipped].switch_to("jmp"),
+        Some(op) => return Err(Error::Certificate(format!("instruction {} is `{}`, not `jmp` or `nop`", flipped, op))),
+        None => return Err(Error::Certificate(format!("the program has no instruction {}", flipped))),
+    }
+    let (normal_exit, accum) = match run_program(&mut patched, 1) {
+        Ok(result) => result,
+        Err(Error::NoAnswer(msg)) => return Err(Error::Certificate(format!("with instruction {} flipped, {}", flipped, msg))),
+        Err(err) => return Err(err),
+    };
+    if !normal_exit {
+        return Err(Error::Certificate(format!("flipping instruction {} still loops", flipped)));
+    }
+    if accum.to_string() != answer {
+        return Err(Error::Certificate(format!("the fixed program ends with {} in the accumulator", accum)));
+    }
+    return Ok(());
+}
+
+pub fn list_program(prog: &Vec<Instr>) {
+    prog.iter().for_each(|x| println!("{} {}", x.op, x.operand));
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Instr>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const VERSION: u32 = 4;
+
+    fn parse(text: &str) -> Result<Vec<Instr>, Error> {
+        let lines = to_lines(text);
+        Ok(parse_program(&lines)?)
+    }
+
+    fn part1(prog: &Vec<Instr>) -> Result<Certified<i32>, Error> {
+        let mut executed = Vec::new();
+        let (finished, accum) = run_program_traced(&mut prog.to_vec(), 1, &mut executed)?;
+        if finished {
+            return Err(Error::NoAnswer("the program finishes without looping".to_string()));
+        }
+        Ok(Certified::new(accum, json!({ "executed": executed })))
+    }
+
+    fn part2(prog: &Vec<Instr>) -> Result<Certified<i32>, Error> {
+        let (is_fixed, mut fixed) = fix_program(prog.to_vec())?;
+        if !is_fixed {
+            return Err(Error::NoAnswer("program is unfixable".to_string()));
+        }
+        let flipped = prog.iter().zip(fixed.iter()).position(|(orig, instr)| orig.op != instr.op);
+        let (_, accum) = run_program(&mut fixed, 1)?;
+        Ok(Certified::new(accum, json!({ "flipped": flipped })))
+    }
+
+    fn check(prog: &Vec<Instr>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        match part {
+            1 => check_loop(prog, &certificate_field(certificate, "executed")?, answer),
+            _ => check_flip(prog, certificate_field(certificate, "flipped")?, answer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test1() {
+        assert_eq!(1, 1);
+    }
+
+    #[test]
+    fn test_check_flip() {
+        let prog = Day8::parse("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n").unwrap();
+        let certificate = Day8::part2(&prog).unwrap().certificate;
+        assert_eq!(certificate, json!({ "flipped": 7 }));
+        assert!(Day8::check(&prog, 2, &certificate, "8").is_ok());
+
+        let err = Day8::check(&prog, 2, &json!({ "flipped": 2 }), "8").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: flipping instruction 2 still loops");
+        let err = Day8::check(&prog, 2, &json!({ "flipped": 1 }), "8").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: instruction 1 is `acc`, not `jmp` or `nop`");
+        let err = Day8::check(&prog, 2, &certificate, "7").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the fixed program ends with 8 in the accumulator");
+    }
+
+    #[test]
+    fn test_check_loop() {
+        let prog = Day8::parse("nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n").unwrap();
+        let solved = Day8::part1(&prog).unwrap();
+        assert_eq!(solved.certificate, json!({ "executed": [0, 1, 2, 6, 7, 3, 4] }));
+        assert!(Day8::check(&prog, 1, &solved.certificate, "5").is_ok());
+
+        let err = Day8::check(&prog, 1, &json!({ "executed": [0, 1, 2, 6, 7, 3] }), "5").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the program goes on to instruction 4, which has not run");
+        let err = Day8::check(&prog, 1, &json!({ "executed": [0, 1, 2, 3] }), "4").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: instruction 3 runs where 6 should");
+        let err = Day8::check(&prog, 1, &json!({ "executed": [0, 1, 2, 6, 7, 3, 4, 1] }), "6").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: instruction 1 repeats before the end");
+        let prog = Day8::parse("jmp +2\nacc +1\n").unwrap();
+        let err = Day8::check(&prog, 1, &json!({ "executed": [0, 2] }), "0").unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the program has no instruction 2");
+    }
+
+    #[test]
+    fn test_jump_outside_program() {
+        let prog = Day8::parse("jmp -5\n").unwrap();
+        let err = Day8::part1(&prog).err().unwrap();
+        assert_eq!(err.to_string(), "no answer: instruction 0 jumps to -5, outside the program");
+
+        let prog = Day8::parse("nop +10\nacc +1\njmp -2\n").unwrap();
+        assert_eq!(Day8::part2(&prog).unwrap().answer, 1);
+
+        let mut prog = Day8::parse("acc +2147483647\nacc +1\n").unwrap();
+        assert!(matches!(run_program(&mut prog, 1), Err(Error::Overflow(_))));
+    }
+
+    #[test]
+    fn test_parse_instruction_errors() {
+        let err = parse_instruction(&"mul +1".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (1, Some("mul".to_string())));
+        let err = parse_instruction(&"acc +1 ".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (7, Some(" ".to_string())));
+        let err = parse_instruction(&"jmp".to_string()).err().unwrap();
+        assert_eq!((err.column, err.found), (4, None));
+    }
+}
@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use aoc9::{find_variable_window_summing_to, slurp_input, validate_seq};
+
+fn bench_variable_window(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_variable_window_summing_to");
+    for (filename, preamble) in [("example", 5), ("input", 25)].iter() {
+        let numbers = slurp_input(*filename).unwrap();
+        let (_, invalid_num) = validate_seq(&numbers, *preamble).unwrap();
+        group.bench_function(*filename, |b| b.iter(|| find_variable_window_summing_to(&numbers, invalid_num)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_variable_window);
+criterion_main!(benches);
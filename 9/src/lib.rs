@@ -0,0 +1,259 @@
+use itertools::Itertools;
+use log::debug;
+
+use aoc_common::{certificate_field, check_answer, checked_add, count, json, parse_numbers, read_parsed, Certificate, Certified, Details, Error, InputSource, Params, Solution};
+
+pub fn slurp_input<S: Into<InputSource>>(source: S) -> Result<Vec<u128>, Error> {
+    return read_parsed(source, parse_numbers);
+}
+
+pub fn validate_seq(numbers: &Vec<u128>, win_size: usize) -> Result<(bool, u128), Error> {
+    match find_first_invalid(numbers, win_size, &mut Vec::new())? {
+        Some(idx) => Ok((false, numbers[idx])),
+        None => Ok((true, 0)),
+    }
+}
+
+/// The index of the first number that no two of the `win_size` before it sum to.
+/// Records in `pairs` the indices of two that do for each number before it.
+pub fn find_first_invalid(numbers: &Vec<u128>, win_size: usize, pairs: &mut Vec<(usize, usize)>) -> Result<Option<usize>, Error> {
+    let mut checked: u64 = 0;
+    'outer: for idx in win_size..(numbers.len() - win_size) {
+        let last = numbers[idx];
+        for pair in ((idx - win_size)..idx).combinations(2) {
+            checked = checked + 1;
+            if checked_add(&numbers[pair[0]], &numbers[pair[1]], "the sum of two numbers in a window")? == last {
+                pairs.push((pair[0], pair[1]));
+                continue 'outer;
+            }
+        }
+        debug!("broken window before {}: {:?}", last, &numbers[(idx - win_size)..idx]);
+        count("combinations", checked);
+        return Ok(Some(idx));
+    }
+
+    count("combinations", checked);
+    return Ok(None);
+}
+
+pub fn find_window_summing_to(numbers: &Vec<u128>, win_size: usize, expected_sum: u128) -> Result<(bool, usize), Error> {
+    for (idx, win) in numbers.windows(win_size).enumerate() {
+        let mut sum: u128 = 0;
+        for num in win {
+            sum = checked_add(&sum, num, "the sum of a window")?;
+        }
+        if sum == expected_sum {
+            return Ok((true, idx));
+        }
+    }
+    return Ok((false, 0));
+}
+
+pub fn find_variable_window_summing_to(numbers: &Vec<u128>, expected_sum: u128) -> Result<(bool, usize, usize), Error> {
+    for win_size in 2..999 {
+        let (found, offset) = find_window_summing_to(numbers, win_size, expected_sum)?;
+        if found {
+            return Ok((true, win_size, offset));
+        }
+    }
+    return Ok((false, 0, 0));
+}
+
+pub fn find_encryption_weakness(numbers: &Vec<u128>, invalid_num: u128) -> Result<Option<u128>, Error> {
+    let (found, win_size, offset) = find_variable_window_summing_to(numbers, invalid_num)?;
+    if !found {
+        return Ok(None);
+    }
+
+    return Ok(Some(weakness(&numbers[offset..(offset + win_size)])?));
+}
+
+// The smallest and largest numbers in the window added together.
+fn weakness(window: &[u128]) -> Result<u128, Error> {
+    let min = window.iter().min().unwrap();
+    let max = window.iter().max().unwrap();
+    return checked_add(min, max, "the encryption weakness");
+}
+
+/// Confirms that the number at `index` is the first that no two of the
+/// `preamble` numbers before it sum to, as each earlier one has a pair in `pairs`.
+pub fn check_first_invalid(numbers: &Vec<u128>, preamble: usize, certificate: &Certificate) -> Result<usize, Error> {
+    let index: usize = certificate_field(certificate, "index")?;
+    let pairs: Vec<(usize, usize)> = certificate_field(certificate, "pairs")?;
+    if index < preamble || index >= numbers.len() {
+        return Err(Error::Certificate(format!("number {} has no full preamble before it", index)));
+    }
+    if pairs.len() != index - preamble {
+        return Err(Error::Certificate(format!("{} pairs for the {} numbers before {}", pairs.len(), index - preamble, index)));
+    }
+    for (idx, &(a, b)) in (preamble..index).zip(&pairs) {
+        let in_window = |i: usize| i + preamble >= idx && i < idx;
+        if a == b || !in_window(a) || !in_window(b) {
+            return Err(Error::Certificate(format!("{} and {} are not two of the {} numbers before {}", a, b, preamble, idx)));
+        }
+        if checked_add(&numbers[a], &numbers[b], "the sum of a pair")? != numbers[idx] {
+            return Err(Error::Certificate(format!("numbers {} and {} do not sum to number {}", a, b, idx)));
+        }
+    }
+    for a in (index - preamble)..index {
+        for b in (a + 1)..index {
+            if numbers[a].checked_add(numbers[b]) == Some(numbers[index]) {
+                return Err(Error::Certificate(format!("numbers {} and {} sum to number {}", a, b, index)));
+            }
+        }
+    }
+    Ok(index)
+}
+
+/// Confirms that the window of `size` numbers at `offset` sums to the first
+/// invalid number and that its smallest and largest add up to the answer.
+pub fn check_window(numbers: &Vec<u128>, preamble: usize, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+    let invalid = numbers[check_first_invalid(numbers, preamble, certificate)?];
+    let offset: usize = certificate_field(certificate, "offset")?;
+    let size: usize = certificate_field(certificate, "size")?;
+    let window = match numbers.get(offset..offset.saturating_add(size)) {
+        Some(window) if size >= 2 => window,
+        _ => return Err(Error::Certificate(format!("{} numbers at {} are not a window of at least 2", size, offset))),
+    };
+    let mut sum: u128 = 0;
+    for num in window {
+        sum = checked_add(&sum, num, "the sum of the window")?;
+    }
+    if sum != invalid {
+        return Err(Error::Certificate(format!("the window sums to {}, not {}", sum, invalid)));
+    }
+    check_answer(weakness(window)?, answer)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<u128>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(text: &str) -> Result<Vec<u128>, Error> {
+        Ok(parse_numbers(text)?)
+    }
+
+    const PARAMS: &'static [&'static str] = &["preamble"];
+
+    const VERSION: u32 = 2;
+
+    fn part1(numbers: &Vec<u128>) -> Result<Certified<u128>, Error> {
+        Day9::part1_with(numbers, &Params::new())
+    }
+
+    fn part2(numbers: &Vec<u128>) -> Result<Certified<u128>, Error> {
+        Day9::part2_with(numbers, &Params::new())
+    }
+
+    fn part1_with(numbers: &Vec<u128>, params: &Params) -> Result<Certified<u128>, Error> {
+        let preamble: usize = params.get_or("preamble", 25)?;
+        if numbers.len() < preamble {
+            return Err(Error::NoAnswer(format!("fewer numbers than the {} number preamble", preamble)));
+        }
+        let mut pairs = Vec::new();
+        match find_first_invalid(numbers, preamble, &mut pairs)? {
+            Some(idx) => Ok(Certified::new(numbers[idx], json!({ "index": idx, "pairs": pairs }))),
+            None => Err(Error::NoAnswer("all numbers are valid".to_string())),
+        }
+    }
+
+    fn part2_with(numbers: &Vec<u128>, params: &Params) -> Result<Certified<u128>, Error> {
+        let Certified{ answer: invalid_num, mut certificate } = Day9::part1_with(numbers, params)?;
+        let (found, win_size, offset) = find_variable_window_summing_to(numbers, invalid_num)?;
+        if !found {
+            return Err(Error::NoAnswer("no target window found".to_string()));
+        }
+        certificate["offset"] = json!(offset);
+        certificate["size"] = json!(win_size);
+        Ok(Certified::new(weakness(&numbers[offset..(offset + win_size)])?, certificate))
+    }
+
+    fn check(numbers: &Vec<u128>, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error> {
+        Day9::check_with(numbers, part, certificate, answer, &Params::new())
+    }
+
+    fn check_with(numbers: &Vec<u128>, part: u8, certificate: &Certificate, answer: &str, params: &Params) -> Result<(), Error> {
+        let preamble: usize = params.get_or("preamble", 25)?;
+        match part {
+            1 => {
+                let index = check_first_invalid(numbers, preamble, certificate)?;
+                check_answer(numbers[index], answer)
+            },
+            _ => check_window(numbers, preamble, certificate, answer),
+        }
+    }
+
+    fn details(numbers: &Vec<u128>, part: u8) -> Result<Details, Error> {
+        Day9::details_with(numbers, part, &Params::new())
+    }
+
+    fn details_with(numbers: &Vec<u128>, part: u8, params: &Params) -> Result<Details, Error> {
+        let invalid_num = Day9::part1_with(numbers, params)?.answer;
+        let mut details = Details::new();
+        details.insert("invalid_number".to_string(), json!(invalid_num));
+        if part == 2 {
+            let (_, win_size, offset) = find_variable_window_summing_to(numbers, invalid_num)?;
+            let window = &numbers[offset..offset + win_size];
+            details.insert("window".to_string(), json!({
+                "offset": offset,
+                "size": win_size,
+                "min": window.iter().min(),
+                "max": window.iter().max(),
+            }));
+        }
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let numbers = slurp_input("example").unwrap();
+        let (valid, last_num) = validate_seq(&numbers, 5).unwrap();
+        assert_eq!(valid, false);
+        assert_eq!(last_num, 127);
+
+        let (found, offset) = find_window_summing_to(&numbers, 4, last_num).unwrap();
+        assert_eq!(found, true);
+        assert_eq!(offset, 2);
+
+        let (_found1, win_size, _offset1) = find_variable_window_summing_to(&numbers, last_num).unwrap();
+        assert_eq!(win_size, 4);
+        assert_eq!(offset, 2);
+    }
+
+    #[test]
+    fn test_check_window() {
+        let numbers = slurp_input("example").unwrap();
+        let mut params = Params::new();
+        params.set("preamble", "5");
+        let solved = Day9::part2_with(&numbers, &params).unwrap();
+        assert_eq!((solved.certificate["index"].clone(), solved.certificate["offset"].clone()), (json!(14), json!(2)));
+        assert!(Day9::check_with(&numbers, 2, &solved.certificate, "62", &params).is_ok());
+        assert!(Day9::check_with(&numbers, 1, &solved.certificate, "127", &params).is_ok());
+
+        let mut early = solved.certificate.clone();
+        early["index"] = json!(13);
+        early["pairs"].as_array_mut().unwrap().pop();
+        let err = Day9::check_with(&numbers, 1, &early, "117", &params).unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: numbers 8 and 11 sum to number 13");
+        let mut short = solved.certificate.clone();
+        short["size"] = json!(3);
+        let err = Day9::check_with(&numbers, 2, &short, "62", &params).unwrap_err();
+        assert_eq!(err.to_string(), "certificate rejected: the window sums to 87, not 127");
+    }
+
+    #[test]
+    fn test_window_overflow() {
+        let numbers: Vec<u128> = vec![u128::MAX - 1, 2, 3, 4, 1];
+        assert!(matches!(validate_seq(&numbers, 2), Err(Error::Overflow(_))));
+        assert!(matches!(find_window_summing_to(&numbers, 3, 9), Err(Error::Overflow(_))));
+    }
+}
+
@@ -0,0 +1,50 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Known-correct answers for a crate's `input`, read from its `answers.toml`.
+/// Answers are strings so any displayable answer can be recorded exactly.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn part(&self, n: u8) -> Option<&str> {
+        match n {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_answers(text: &str) -> Result<Answers, String> {
+    toml::from_str(text).map_err(|err| err.to_string())
+}
+
+/// Loads the answers file, treating a missing file as having no answers.
+pub fn load_answers(path: &Path) -> Result<Answers, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_answers(&text).map_err(|err| format!("{}: {}", path.display(), err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(err) => Err(format!("cannot read {}: {}.", path.display(), err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("part1 = \"2386\"\n").unwrap();
+        assert_eq!(answers.part(1), Some("2386"));
+        assert_eq!(answers.part(2), None);
+        assert!(parse_answers("part3 = \"1\"\n").is_err());
+    }
+}
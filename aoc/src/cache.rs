@@ -0,0 +1,160 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::{Certificate, Counters, Details};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::days::PartResult;
+
+/// What a cached answer was solved from. An entry is only used when all of it matches.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Key<'a> {
+    pub day: &'a str,
+    pub part: u8,
+    pub input_hash: String,
+    pub version: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    day: String,
+    part: u8,
+    input_hash: String,
+    version: u32,
+    answer: String,
+    details: Details,
+    elapsed_ms: f64,
+    #[serde(default)]
+    certificate: Option<Certificate>,
+    #[serde(default)]
+    counters: Counters,
+}
+
+/// SHA-256 of the puzzle input, in hex.
+pub fn hash_input(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Answers saved between runs, one file per day and part. Solving a part
+/// again with a different input or solver version replaces its file.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &Path) -> Cache {
+        Cache{ dir: dir.to_path_buf() }
+    }
+
+    fn entry_path(&self, day: &str, part: u8) -> PathBuf {
+        self.dir.join(format!("day{}-part{}.json", day, part))
+    }
+
+    /// The cached result for `key`. Missing, stale and unreadable entries are all misses.
+    pub fn get(&self, key: &Key) -> Option<PartResult> {
+        let path = self.entry_path(key.day, key.part);
+        let text = fs::read_to_string(&path).ok()?;
+        let entry: Entry = match serde_json::from_str(&text) {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!("ignoring unreadable cache entry {}: {}", path.display(), err);
+                return None;
+            },
+        };
+        if entry.day != key.day || entry.part != key.part || entry.input_hash != key.input_hash || entry.version != key.version {
+            debug!("cache entry {} is stale", path.display());
+            return None;
+        }
+
+        let elapsed = Duration::from_secs_f64(entry.elapsed_ms.max(0.0) / 1000.0);
+        Some(PartResult{ part: entry.part, answer: entry.answer, details: entry.details, elapsed: elapsed, cached: true, certificate: entry.certificate, counters: entry.counters, memory: None })
+    }
+
+    /// Saves a freshly solved result. Failing to save is only worth a warning.
+    pub fn put(&self, key: &Key, result: &PartResult) {
+        let entry = Entry{
+            day: key.day.to_string(),
+            part: key.part,
+            input_hash: key.input_hash.clone(),
+            version: key.version,
+            answer: result.answer.clone(),
+            details: result.details.clone(),
+            elapsed_ms: result.elapsed.as_secs_f64() * 1000.0,
+            certificate: result.certificate.clone(),
+            counters: result.counters.clone(),
+        };
+        let path = self.entry_path(key.day, key.part);
+        let written = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, serde_json::to_string_pretty(&entry).unwrap() + "\n"));
+        if let Err(err) = written {
+            warn!("cannot write cache entry {}: {}", path.display(), err);
+        }
+    }
+
+    /// Removes every cached answer, returning how many there were.
+    pub fn clear(&self) -> Result<usize, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(format!("cannot read {}: {}.", self.dir.display(), err)),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry.map_err(|err| err.to_string())?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                fs::remove_file(&path).map_err(|err| format!("cannot remove {}: {}.", path.display(), err))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::Counter;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_round_trip_and_invalidation() {
+        let dir = scratch_dir("round-trip");
+        let cache = Cache::new(&dir);
+        let key = Key{ day: "15", part: 2, input_hash: hash_input("0,3,6\n"), version: 1 };
+        assert!(cache.get(&key).is_none());
+
+        let counters = Counters::from([("turns".to_string(), Counter::Total(2020))]);
+        let result = PartResult{ part: 2, answer: "175594".to_string(), details: Details::new(), elapsed: Duration::from_millis(5), cached: false, certificate: Some(serde_json::json!({ "timestamp": "1068781" })), counters: counters, memory: None };
+        cache.put(&key, &result);
+        let hit = cache.get(&key).unwrap();
+        assert_eq!((hit.answer.as_str(), hit.cached), ("175594", true));
+        assert_eq!(hit.certificate, result.certificate);
+        assert_eq!(hit.counters, result.counters);
+
+        let changed_input = Key{ input_hash: hash_input("1,3,2\n"), ..key.clone() };
+        assert!(cache.get(&changed_input).is_none());
+        let new_version = Key{ version: 2, ..key.clone() };
+        assert!(cache.get(&new_version).is_none());
+
+        assert_eq!(cache.clear(), Ok(1));
+        assert!(cache.get(&key).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_clear_without_cache_dir() {
+        assert_eq!(Cache::new(&scratch_dir("missing")).clear(), Ok(0));
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::{memory_usage, take_counters, track_memory, Certificate, Counters, Details, Error, MemoryUsage, Params, Simulation, Solution};
+
+/// Parses a puzzle input once and solves the requested parts of it.
+pub type RunFn = fn(&str, &[u8], &Params) -> Result<Vec<PartResult>, Error>;
+
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub details: Details,
+    /// Time spent solving the part, not counting parsing or working out details.
+    pub elapsed: Duration,
+    /// Whether the result came from the answer cache rather than being solved just now.
+    pub cached: bool,
+    /// Evidence for the answer. Parts solved with `Certified::unchecked`, and answers
+    /// cached before certificates were kept, lack one.
+    pub certificate: Option<Certificate>,
+    /// Operations counted while solving the part.
+    pub counters: Counters,
+    /// Heap used while solving the part, when built with the `track-alloc` feature.
+    /// Cached results leave it out.
+    pub memory: Option<MemoryUsage>,
+}
+
+/// Parses a puzzle input and checks a certificate for the answer to one part.
+pub type CheckFn = fn(&str, u8, &Certificate, &str, &Params) -> Result<(), Error>;
+
+/// Generates a random valid puzzle input of a given size from a seed.
+pub type GenerateFn = fn(usize, u64) -> String;
+
+/// Sets up a step-by-step playback of one part of a puzzle input.
+pub type SimulateFn = fn(&str, u8) -> Result<Box<dyn Simulation>, Error>;
+
+/// One crate's solution and the parts of the day it answers.
+pub struct Solver {
+    /// Directory, relative to the workspace root, holding the crate and its `input`.
+    pub dir: &'static str,
+    pub parts: &'static [u8],
+    pub run: RunFn,
+    /// The solution's `Solution::VERSION`, recorded with cached answers.
+    pub version: u32,
+    pub check: CheckFn,
+}
+
+pub struct Day {
+    pub name: &'static str,
+    pub solvers: Vec<Solver>,
+    pub generate: Option<GenerateFn>,
+    pub simulate: Option<SimulateFn>,
+}
+
+impl Day {
+    fn with_generator(mut self, generate: GenerateFn) -> Day {
+        self.generate = Some(generate);
+        self
+    }
+
+    fn with_simulation(mut self, simulate: SimulateFn) -> Day {
+        self.simulate = Some(simulate);
+        self
+    }
+
+    pub fn solver_for(&self, part: u8) -> Option<&Solver> {
+        self.solvers.iter().find(|s| s.parts.contains(&part))
+    }
+}
+
+pub fn run_parts<S: Solution>(text: &str, parts: &[u8], params: &Params) -> Result<Vec<PartResult>, Error> {
+    if let Some(name) = params.names().find(|name| !S::PARAMS.contains(name)) {
+        return Err(Error::Param(format!("`{}` is not a parameter of this day", name)));
+    }
+
+    let input = S::parse(text)?;
+    let mut results = Vec::new();
+    for &n in parts {
+        take_counters();
+        track_memory();
+        let start = Instant::now();
+        let (answer, certificate): (String, Certificate) = match n {
+            1 => {
+                let solved = S::part1_with(&input, params)?;
+                (solved.answer.to_string(), solved.certificate)
+            },
+            2 => {
+                let solved = S::part2_with(&input, params)?;
+                (solved.answer.to_string(), solved.certificate)
+            },
+            _ => return Err(Error::Unsolved),
+        };
+        let elapsed = start.elapsed();
+        let memory = memory_usage();
+        let counters = take_counters();
+        let details = S::details_with(&input, n, params)?;
+        results.push(PartResult{ part: n, answer: answer, details: details, elapsed: elapsed, cached: false, certificate: if certificate.is_null() { None } else { Some(certificate) }, counters: counters, memory: memory });
+    }
+    Ok(results)
+}
+
+pub fn check_part<S: Solution>(text: &str, part: u8, certificate: &Certificate, answer: &str, params: &Params) -> Result<(), Error> {
+    let input = S::parse(text)?;
+    S::check_with(&input, part, certificate, answer, params)
+}
+
+const BOTH: &[u8] = &[1, 2];
+
+fn solver<S: Solution>(dir: &'static str, parts: &'static [u8]) -> Solver {
+    Solver{ dir: dir, parts: parts, run: run_parts::<S>, version: S::VERSION, check: check_part::<S> }
+}
+
+fn day<S: Solution>(name: &'static str, parts: &'static [u8]) -> Day {
+    Day{ name: name, solvers: vec![solver::<S>(name, parts)], generate: None, simulate: None }
+}
+
+pub fn all_days() -> Vec<Day> {
+    vec![
+        Day{
+            name: "1",
+            solvers: vec![
+                solver::<aoc1_1::Day1>("1.1", &[1]),
+                solver::<aoc1_2::Day1>("1.2", &[2]),
+            ],
+            generate: None,
+            simulate: None,
+        },
+        day::<aoc2::Day2>("2", BOTH).with_generator(aoc2::generator::generate),
+        day::<aoc3::Day3>("3", BOTH),
+        day::<aoc4::Day4>("4", BOTH).with_generator(aoc4::generator::generate),
+        day::<aoc5::Day5>("5", BOTH),
+        day::<aoc6::Day6>("6", BOTH),
+        day::<aoc7::Day7>("7", BOTH).with_generator(aoc7::generator::generate),
+        day::<aoc8::Day8>("8", BOTH).with_generator(aoc8::generator::generate),
+        day::<aoc9::Day9>("9", BOTH),
+        day::<aoc10::Day10>("10", BOTH),
+        day::<aoc11::Day11>("11", BOTH).with_simulation(aoc11::simulation::simulate),
+        day::<aoc12::Day12>("12", BOTH).with_simulation(aoc12::simulation::simulate),
+        day::<aoc13::Day13>("13", BOTH),
+        day::<aoc14::Day14>("14", BOTH),
+        day::<aoc15::Day15>("15", BOTH),
+        day::<aoc16::Day16>("16", BOTH).with_generator(aoc16::generator::generate),
+        day::<aoc17::Day17>("17", BOTH).with_simulation(aoc17::simulation::simulate),
+        day::<aoc18::Day18>("18", &[1]),
+        day::<aoc19::Day19>("19", &[1]).with_generator(aoc19::generator::generate),
+    ]
+}
+
+pub fn find_day(name: &str) -> Option<Day> {
+    all_days().into_iter().find(|d| d.name == name)
+}
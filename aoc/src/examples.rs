@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aoc_common::Params;
+use serde::Deserialize;
+
+use crate::days::Solver;
+
+/// An example input from a crate's `examples.toml`, with the parameters to solve it
+/// with and the answers it should give. Parts without an answer are not checked.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    /// File in the crate's directory holding the input.
+    pub file: Option<String>,
+    /// The input itself, for examples only given in the puzzle text.
+    pub input: Option<String>,
+    #[serde(default)]
+    pub params: BTreeMap<String, toml::Value>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default)]
+    example: Vec<Example>,
+}
+
+impl Example {
+    /// How failures refer to the example: its file, or the first line of its input.
+    pub fn name(&self) -> String {
+        match (&self.file, &self.input) {
+            (Some(file), _) => file.clone(),
+            (None, Some(input)) => format!("`{}`", input.lines().next().unwrap_or("")),
+            (None, None) => "example without input".to_string(),
+        }
+    }
+
+    pub fn params(&self) -> Params {
+        let mut params = Params::new();
+        for (name, value) in &self.params {
+            match value {
+                toml::Value::String(text) => params.set(name, text),
+                other => params.set(name, &other.to_string()),
+            }
+        }
+        params
+    }
+
+    pub fn answer(&self, n: u8) -> Option<&str> {
+        match n {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_manifest(text: &str) -> Result<Vec<Example>, String> {
+    let manifest: Manifest = toml::from_str(text).map_err(|err| err.to_string())?;
+    for example in &manifest.example {
+        if example.file.is_some() == example.input.is_some() {
+            return Err(format!("{} needs either `file` or `input`, not both.", example.name()));
+        }
+    }
+    Ok(manifest.example)
+}
+
+/// Loads a manifest, treating a missing file as having no examples.
+pub fn load_manifest(path: &Path) -> Result<Vec<Example>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_manifest(&text).map_err(|err| format!("{}: {}", path.display(), err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(format!("cannot read {}: {}.", path.display(), err)),
+    }
+}
+
+/// Solves `example` with the solver whose crate lives in `dir`, describing each
+/// answer that is wrong, could not be worked out, or has a certificate that does not check out.
+pub fn check_example(solver: &Solver, dir: &Path, example: &Example) -> Vec<String> {
+    let text = match (&example.file, &example.input) {
+        (Some(file), _) => match fs::read_to_string(dir.join(file)) {
+            Ok(text) => text,
+            Err(err) => return vec![format!("{}/{}: cannot read: {}", solver.dir, file, err)],
+        },
+        (None, input) => input.clone().unwrap_or_default(),
+    };
+    let name = format!("{} {}", solver.dir, example.name());
+
+    let mut failures = Vec::new();
+    let mut parts = Vec::new();
+    for n in [1, 2] {
+        if example.answer(n).is_none() {
+            continue;
+        }
+        if solver.parts.contains(&n) {
+            parts.push(n);
+        } else {
+            failures.push(format!("{}: part {} is not solved in this crate", name, n));
+        }
+    }
+
+    let params = example.params();
+    match (solver.run)(&text, &parts, &params) {
+        Ok(results) => {
+            for result in results {
+                let expected = example.answer(result.part).unwrap_or("");
+                if result.answer != expected {
+                    failures.push(format!("{} part {}: expected {}, got {}", name, result.part, expected, result.answer));
+                }
+                // Fresh results only lack a certificate when the part is solved unchecked.
+                if let Some(certificate) = &result.certificate {
+                    if let Err(err) = (solver.check)(&text, result.part, certificate, &result.answer, &params) {
+                        failures.push(format!("{} part {}: {}", name, result.part, err));
+                    }
+                }
+            }
+        },
+        Err(err) => failures.push(format!("{}: {}", name, err)),
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::days::{all_days, find_day};
+
+    #[test]
+    fn test_parse_manifest() {
+        let text = "[[example]]\nfile = \"example\"\nparams = { preamble = 5 }\npart1 = \"127\"\n\n[[example]]\ninput = \"0,3,6\"\n";
+        let examples = parse_manifest(text).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].params().get_or("preamble", 25).unwrap(), 5);
+        assert_eq!((examples[0].answer(1), examples[0].answer(2)), (Some("127"), None));
+        assert_eq!(examples[1].name(), "`0,3,6`");
+
+        assert!(parse_manifest("[[example]]\npart1 = \"1\"\n").is_err());
+        assert!(parse_manifest("[[example]]\ninput = \"1\"\npart3 = \"1\"\n").is_err());
+    }
+
+    #[test]
+    fn test_wrong_answers_and_unknown_params() {
+        let day = find_day("9").unwrap();
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../9");
+        let examples = parse_manifest("[[example]]\nfile = \"example\"\nparams = { preamble = 5 }\npart1 = \"126\"\n\n[[example]]\nfile = \"example\"\nparams = { preambel = 5 }\npart1 = \"127\"\n").unwrap();
+        assert_eq!(check_example(&day.solvers[0], &dir, &examples[0]), vec!["9 example part 1: expected 126, got 127"]);
+        assert_eq!(check_example(&day.solvers[0], &dir, &examples[1]), vec!["9 example: invalid parameter: `preambel` is not a parameter of this day"]);
+    }
+
+    #[test]
+    fn test_every_example() {
+        let mut checked = 0;
+        let mut failures = Vec::new();
+        for day in all_days() {
+            for solver in &day.solvers {
+                let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(solver.dir);
+                let examples = load_manifest(&dir.join("examples.toml")).unwrap();
+                for example in &examples {
+                    failures.extend(check_example(solver, &dir, example));
+                }
+                checked = checked + examples.len();
+            }
+        }
+        assert!(checked > 0);
+        assert!(failures.is_empty(), "{} example answer(s) wrong:\n{}", failures.len(), failures.join("\n"));
+    }
+
+    // `test_every_example` only vouches for the certificates of parts some example answers.
+    #[test]
+    fn test_every_part_has_an_example() {
+        let mut missing = Vec::new();
+        for day in all_days() {
+            for solver in &day.solvers {
+                let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(solver.dir);
+                let examples = load_manifest(&dir.join("examples.toml")).unwrap();
+                for &n in solver.parts {
+                    if !examples.iter().any(|example| example.answer(n).is_some()) {
+                        missing.push(format!("{} part {}", solver.dir, n));
+                    }
+                }
+            }
+        }
+        assert!(missing.is_empty(), "no example for {}", missing.join(", "));
+    }
+}
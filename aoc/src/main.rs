@@ -0,0 +1,515 @@
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{format_bytes, init_logging, with_progress, CancelToken, json, memory_usage, InputSource, Params};
+use clap::{Parser, Subcommand, ValueEnum};
+use log::debug;
+
+use cache::Cache;
+
+// Measures the heap each part uses, at some cost to speed.
+#[cfg(feature = "track-alloc")]
+#[global_allocator]
+static ALLOCATOR: aoc_common::TrackingAllocator = aoc_common::TrackingAllocator;
+
+mod answers;
+mod cache;
+mod days;
+mod examples;
+mod parallel;
+mod progress;
+mod viz;
+
+/// Runs the Advent of Code 2020 solutions from one place.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    /// Log filter, e.g. `debug` or `aoc16=debug,aoc8=trace`. Overrides `RUST_LOG`.
+    #[arg(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day's puzzle.
+    Run {
+        /// Day to solve, e.g. `1` or `17`.
+        day: String,
+
+        /// Only solve the given part.
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Puzzle input to read instead of the day's `input` file, or `-` for stdin.
+        #[arg(long, value_name = "PATH")]
+        input: Option<String>,
+
+        /// Puzzle input given directly, e.g. `--inline 0,3,6` for day 15.
+        #[arg(long, value_name = "TEXT", conflicts_with = "input")]
+        inline: Option<String>,
+
+        /// How to print each part's result.
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Solve every part again instead of using cached answers.
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Print the operations each part counted, e.g. VM steps for day 8.
+        /// JSON output always includes them.
+        #[arg(long)]
+        counters: bool,
+
+        /// Give up on solving after this many seconds, e.g. `2.5`.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// Print a random puzzle input for a day, e.g. to pipe into `run --input -`.
+    Gen {
+        /// Day to generate input for.
+        day: String,
+
+        /// How big an input to generate, e.g. the number of lines or records.
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed for the random generator. The same seed gives the same input.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Solve each day's `input` and compare against its `answers.toml`.
+    Verify {
+        /// Only verify the given day.
+        day: Option<String>,
+
+        /// Solve every part again instead of using cached answers.
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Check the certificate behind each answer without solving the part again.
+    Check {
+        /// Only check the given day.
+        day: Option<String>,
+
+        /// Solve every part again instead of using cached answers and certificates.
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Solve the examples listed in each crate's `examples.toml` and check their answers.
+    Examples {
+        /// Only check the given day.
+        day: Option<String>,
+    },
+    /// Solve every day's parts in parallel and print a table of answers and timings.
+    All {
+        /// Number of worker threads. Defaults to the number of CPUs.
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Give up on each part after this many seconds.
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+    },
+    /// Remove every cached answer.
+    ClearCache,
+    /// Step through a day's generations or instructions in the terminal.
+    Viz {
+        /// Day to play back: 11, 12 or 17.
+        day: String,
+
+        /// Which part's rules to play back.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// Puzzle input to read instead of the day's `input` file.
+        #[arg(long, value_name = "PATH")]
+        input: Option<String>,
+
+        /// Puzzle input given directly.
+        #[arg(long, value_name = "TEXT", conflicts_with = "input")]
+        inline: Option<String>,
+
+        /// Frames per second when playing, from 1 to 64.
+        #[arg(long, default_value_t = 4)]
+        fps: u32,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// One `day N part N: answer` line per part.
+    Text,
+    /// One JSON object per line and part, with intermediate values and timings.
+    Json,
+}
+
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    let secs: f64 = arg.parse().map_err(|_| format!("`{}` is not a number of seconds", arg))?;
+    if !secs.is_finite() || secs <= 0.0 {
+        return Err("the timeout must be more than 0 seconds".to_string());
+    }
+    Ok(Duration::from_secs_f64(secs))
+}
+
+fn day_dir(dir: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(dir)
+}
+
+fn default_input_path(dir: &str) -> PathBuf {
+    day_dir(dir).join("input")
+}
+
+fn cache_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("target").join("aoc-cache")
+}
+
+fn read_source(source: &InputSource) -> Result<String, String> {
+    source.read().map_err(|err| err.to_string())
+}
+
+/// Solves `parts` of the input, taking any answers already in `cache` and saving the rest to it.
+fn solve(day_name: &str, solver: &days::Solver, source: &InputSource, text: &str, parts: &[u8], cache: Option<&Cache>) -> Result<Vec<days::PartResult>, String> {
+    let input_hash = cache::hash_input(text);
+    let key = |n: u8| cache::Key{ day: day_name, part: n, input_hash: input_hash.clone(), version: solver.version };
+
+    let mut results = Vec::new();
+    let mut unsolved = Vec::new();
+    for &n in parts {
+        match cache.and_then(|cache| cache.get(&key(n))) {
+            Some(result) => {
+                debug!("day {} part {}: using cached answer", day_name, n);
+                results.push(result);
+            },
+            None => unsolved.push(n),
+        }
+    }
+
+    if !unsolved.is_empty() {
+        let solved = (solver.run)(text, &unsolved, &Params::new()).map_err(|err| err.in_file(&source.to_string()).to_string())?;
+        if let Some(cache) = cache {
+            for result in &solved {
+                cache.put(&key(result.part), result);
+            }
+        }
+        results.extend(solved);
+    }
+    results.sort_by_key(|result| result.part);
+    Ok(results)
+}
+
+fn print_result(day: &days::Day, result: &days::PartResult, format: Format, counters: bool) {
+    match format {
+        Format::Text => {
+            println!("day {} part {}: {}", day.name, result.part, result.answer);
+            if counters {
+                for (name, counter) in &result.counters {
+                    println!("  {}: {}", name, counter);
+                }
+            }
+            if let Some(memory) = result.memory {
+                println!("  heap: {}", memory);
+            }
+        },
+        Format::Json => {
+            let value = json!({
+                "day": day.name,
+                "part": result.part,
+                "answer": result.answer,
+                "elapsed_ms": result.elapsed.as_secs_f64() * 1000.0,
+                "cached": result.cached,
+                "details": result.details,
+                "certificate": result.certificate,
+                "counters": result.counters,
+                "memory": result.memory,
+            });
+            println!("{}", value);
+        },
+    }
+}
+
+fn run(day_name: &str, part: Option<u8>, given: Option<InputSource>, format: Format, counters: bool, timeout: Option<Duration>, cache: Option<&Cache>) -> Result<(), String> {
+    let day = days::find_day(day_name).ok_or(format!("unknown day '{}'.", day_name))?;
+    if let Some(n) = part {
+        if day.solver_for(n).is_none() {
+            return Err(format!("day {} part {} is not solved.", day.name, n));
+        }
+    }
+
+    // Read a given input once up front, as day 1 has a solver per part and stdin can't be read twice.
+    let given = match given {
+        Some(source) => {
+            let text = read_source(&source)?;
+            Some((source, text))
+        },
+        None => None,
+    };
+
+    for solver in &day.solvers {
+        let parts: Vec<u8> = solver.parts.iter()
+            .cloned()
+            .filter(|n| part.is_none() || part == Some(*n))
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+
+        let (source, text) = match &given {
+            Some((source, text)) => (source.clone(), text.clone()),
+            None => {
+                let source = InputSource::File(default_input_path(solver.dir));
+                let text = read_source(&source)?;
+                (source, text)
+            },
+        };
+        // The time budget covers each crate's parts together, and cached answers take none of it.
+        let token = timeout.map(CancelToken::with_timeout).unwrap_or_default();
+        let display = Rc::new(progress::ProgressDisplay::new(format!("day {}", day.name)));
+        let results = token.run(|| with_progress(display.clone(), || solve(day.name, solver, &source, &text, &parts, cache)));
+        display.finish();
+        for result in results? {
+            print_result(&day, &result, format, counters);
+        }
+    }
+
+    Ok(())
+}
+
+fn generate(day_name: &str, size: usize, seed: u64) -> Result<(), String> {
+    let day = days::find_day(day_name).ok_or(format!("unknown day '{}'.", day_name))?;
+    let generate = day.generate.ok_or(format!("day {} has no input generator.", day.name))?;
+    print!("{}", generate(size, seed));
+    Ok(())
+}
+
+fn verify(day_name: Option<String>, cache: Option<&Cache>) -> Result<(), String> {
+    let days = match day_name {
+        Some(name) => vec![days::find_day(&name).ok_or(format!("unknown day '{}'.", name))?],
+        None => days::all_days(),
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in &days {
+        for solver in &day.solvers {
+            let expected = answers::load_answers(&day_dir(solver.dir).join("answers.toml"))?;
+            let source = InputSource::File(default_input_path(solver.dir));
+            let results = match read_source(&source).and_then(|text| solve(day.name, solver, &source, &text, solver.parts, cache)) {
+                Ok(results) => results,
+                Err(err) => {
+                    for n in solver.parts {
+                        println!("day {} part {}: FAIL: {}", day.name, n, err);
+                        failed += 1;
+                    }
+                    continue;
+                }
+            };
+
+            for days::PartResult{ part: n, answer, .. } in results {
+                match expected.part(n) {
+                    Some(known) if known == answer => {
+                        println!("day {} part {}: pass", day.name, n);
+                        passed += 1;
+                    },
+                    Some(known) => {
+                        println!("day {} part {}: FAIL: expected {}, got {}", day.name, n, known, answer);
+                        failed += 1;
+                    },
+                    None => {
+                        println!("day {} part {}: missing answer, got {}", day.name, n, answer);
+                        missing += 1;
+                    },
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        return Err(format!("{} part(s) did not match their recorded answers.", failed));
+    }
+    Ok(())
+}
+
+fn check(day_name: Option<String>, cache: Option<&Cache>) -> Result<(), String> {
+    let days = match day_name {
+        Some(name) => vec![days::find_day(&name).ok_or(format!("unknown day '{}'.", name))?],
+        None => days::all_days(),
+    };
+
+    let (mut certified, mut rejected, mut unchecked) = (0, 0, 0);
+    for day in &days {
+        for solver in &day.solvers {
+            let source = InputSource::File(default_input_path(solver.dir));
+            let text = read_source(&source)?;
+            for result in solve(day.name, solver, &source, &text, solver.parts, cache)? {
+                let certificate = match &result.certificate {
+                    Some(certificate) => certificate,
+                    None => {
+                        println!("day {} part {}: no certificate", day.name, result.part);
+                        unchecked += 1;
+                        continue;
+                    },
+                };
+                let start = Instant::now();
+                match (solver.check)(&text, result.part, certificate, &result.answer, &Params::new()) {
+                    Ok(()) => {
+                        let ms = start.elapsed().as_secs_f64() * 1000.0;
+                        println!("day {} part {}: {} certified in {:.1} ms", day.name, result.part, result.answer, ms);
+                        certified += 1;
+                    },
+                    Err(err) => {
+                        println!("day {} part {}: FAIL: {}", day.name, result.part, err);
+                        rejected += 1;
+                    },
+                }
+            }
+        }
+    }
+
+    println!("{} certified, {} rejected, {} without a certificate", certified, rejected, unchecked);
+    if rejected > 0 {
+        return Err(format!("{} certificate(s) did not prove their answers.", rejected));
+    }
+    Ok(())
+}
+
+fn check_examples(day_name: Option<String>) -> Result<(), String> {
+    let days = match day_name {
+        Some(name) => vec![days::find_day(&name).ok_or(format!("unknown day '{}'.", name))?],
+        None => days::all_days(),
+    };
+
+    let (mut checked, mut failed) = (0, 0);
+    for day in &days {
+        for solver in &day.solvers {
+            let dir = day_dir(solver.dir);
+            for example in examples::load_manifest(&dir.join("examples.toml"))? {
+                let failures = examples::check_example(solver, &dir, &example);
+                if failures.is_empty() {
+                    println!("day {} {}: pass", day.name, example.name());
+                } else {
+                    for failure in &failures {
+                        println!("day {}: FAIL: {}", day.name, failure);
+                    }
+                    failed += 1;
+                }
+                checked += 1;
+            }
+        }
+    }
+
+    println!("{} examples, {} failed", checked, failed);
+    if failed > 0 {
+        return Err(format!("{} example(s) did not give their listed answers.", failed));
+    }
+    Ok(())
+}
+
+fn run_all(jobs: Option<usize>, timeout: Option<Duration>) -> Result<(), String> {
+    let days = days::all_days();
+    let mut queue = Vec::new();
+    for day in &days {
+        for solver in &day.solvers {
+            for &n in solver.parts {
+                let source = InputSource::File(default_input_path(solver.dir));
+                queue.push(parallel::Job{ day: day.name, part: n, source: source, run: solver.run, timeout: timeout });
+            }
+        }
+    }
+
+    let threads = jobs.unwrap_or_else(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+    let start = Instant::now();
+    let results = parallel::run_jobs(queue, threads);
+    let elapsed = start.elapsed();
+
+    // Heap use goes before the status, which can be long, when it was measured at all.
+    let tracked = memory_usage().is_some();
+    let mut header = vec!["day", "part", "answer", "wall", "cpu", "status"];
+    if tracked {
+        header.splice(5..5, ["heap", "allocs"]);
+    }
+
+    let mut rows = Vec::new();
+    let mut failed = 0;
+    for result in &results {
+        let day = days.iter().find(|d| d.name == result.day).unwrap();
+        let dir = day.solver_for(result.part).unwrap().dir;
+        let expected = answers::load_answers(&day_dir(dir).join("answers.toml"))?;
+        let (mut row, fail) = parallel::result_row(result, expected.part(result.part));
+        if fail {
+            failed += 1;
+        }
+        if tracked {
+            let cells = match result.memory {
+                Some(memory) => [format_bytes(memory.peak_bytes), memory.allocations.to_string()],
+                None => ["-".to_string(), "-".to_string()],
+            };
+            row.splice(5..5, cells);
+        }
+        rows.push(row);
+    }
+
+    print!("{}", parallel::format_table(&header, &rows));
+    println!("{} parts on {} threads in {:.1} ms, {} failed", results.len(), threads, elapsed.as_secs_f64() * 1000.0, failed);
+    if failed > 0 {
+        return Err(format!("{} part(s) panicked, failed or did not match their recorded answers.", failed));
+    }
+    Ok(())
+}
+
+fn visualize(day_name: &str, part: u8, given: Option<InputSource>, fps: u32) -> Result<(), String> {
+    let day = days::find_day(day_name).ok_or(format!("unknown day '{}'.", day_name))?;
+    let simulate = day.simulate.ok_or(format!("day {} has no visualization.", day.name))?;
+    let source = match given {
+        Some(source) => source,
+        None => {
+            let solver = day.solver_for(part).ok_or(format!("day {} part {} is not solved.", day.name, part))?;
+            InputSource::File(default_input_path(solver.dir))
+        },
+    };
+    let text = read_source(&source)?;
+    let title = format!("day {} part {}", day.name, part);
+    viz::play(&title, || simulate(&text, part).map_err(|err| err.in_file(&source.to_string()).to_string()), fps)
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_logging(cli.log.as_deref());
+
+    let cache = Cache::new(&cache_dir());
+    let result = match cli.command {
+        Command::Run{ day, part, input, inline, format, no_cache, counters, timeout } => {
+            let given = match (input, inline) {
+                (Some(arg), _) => Some(InputSource::from_arg(&arg)),
+                (None, Some(text)) => Some(InputSource::Inline(text)),
+                (None, None) => None,
+            };
+            run(&day, part, given, format, counters, timeout, if no_cache { None } else { Some(&cache) })
+        },
+        Command::Gen{ day, size, seed } => generate(&day, size, seed),
+        Command::Verify{ day, no_cache } => verify(day, if no_cache { None } else { Some(&cache) }),
+        Command::Check{ day, no_cache } => check(day, if no_cache { None } else { Some(&cache) }),
+        Command::Examples{ day } => check_examples(day),
+        Command::All{ jobs, timeout } => run_all(jobs, timeout),
+        Command::ClearCache => cache.clear().map(|removed| println!("removed {} cached answer(s)", removed)),
+        Command::Viz{ day, part, input, inline, fps } => {
+            let given = match (input, inline) {
+                (Some(arg), _) => Some(InputSource::from_arg(&arg)),
+                (None, Some(text)) => Some(InputSource::Inline(text)),
+                (None, None) => None,
+            };
+            visualize(&day, part, given, fps)
+        },
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,191 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{CancelToken, InputSource, MemoryUsage, Params};
+use cpu_time::ThreadTime;
+
+use crate::days::RunFn;
+
+/// One part of one day, solved on whichever worker thread picks it up.
+pub struct Job {
+    pub day: &'static str,
+    pub part: u8,
+    pub source: InputSource,
+    pub run: RunFn,
+    /// How long the job may take once a worker starts it.
+    pub timeout: Option<Duration>,
+}
+
+pub enum Outcome {
+    Answer(String),
+    /// The input could not be read or the solver returned an error.
+    Failed(String),
+    Panicked(String),
+}
+
+pub struct JobResult {
+    pub day: &'static str,
+    pub part: u8,
+    pub outcome: Outcome,
+    /// Time spent parsing and solving, not counting reading the input.
+    pub wall: Duration,
+    pub cpu: Duration,
+    /// Heap used while solving, when built with the `track-alloc` feature.
+    pub memory: Option<MemoryUsage>,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        return msg.to_string();
+    }
+    if let Some(msg) = payload.downcast_ref::<String>() {
+        return msg.clone();
+    }
+    return "unknown panic".to_string();
+}
+
+fn run_job(job: &Job) -> JobResult {
+    let text = match job.source.read() {
+        Ok(text) => text,
+        Err(err) => {
+            let outcome = Outcome::Failed(err.to_string());
+            return JobResult{ day: job.day, part: job.part, outcome: outcome, wall: Duration::ZERO, cpu: Duration::ZERO, memory: None };
+        },
+    };
+
+    let token = job.timeout.map(CancelToken::with_timeout).unwrap_or_default();
+    let wall_start = Instant::now();
+    let cpu_start = ThreadTime::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| token.run(|| (job.run)(&text, &[job.part], &Params::new()))));
+    let cpu = cpu_start.elapsed();
+    let wall = wall_start.elapsed();
+
+    let (outcome, memory) = match result {
+        Ok(Ok(results)) => match results.into_iter().next() {
+            Some(result) => (Outcome::Answer(result.answer), result.memory),
+            None => (Outcome::Failed("no answer returned".to_string()), None),
+        },
+        Ok(Err(err)) => (Outcome::Failed(err.in_file(&job.source.to_string()).to_string()), None),
+        Err(payload) => (Outcome::Panicked(panic_message(payload)), None),
+    };
+    JobResult{ day: job.day, part: job.part, outcome: outcome, wall: wall, cpu: cpu, memory: memory }
+}
+
+/// Runs the jobs on `threads` worker threads, returning their results in the order given.
+pub fn run_jobs(jobs: Vec<Job>, threads: usize) -> Vec<JobResult> {
+    let queue: Mutex<VecDeque<(usize, Job)>> = Mutex::new(jobs.into_iter().enumerate().collect());
+    let done: Mutex<Vec<(usize, JobResult)>> = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| loop {
+                // Take the lock in its own statement so it is released before solving.
+                let next = queue.lock().unwrap().pop_front();
+                let (idx, job) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                let result = run_job(&job);
+                done.lock().unwrap().push((idx, result));
+            });
+        }
+    });
+
+    let mut done = done.into_inner().unwrap();
+    done.sort_by_key(|(idx, _)| *idx);
+    done.into_iter().map(|(_, result)| result).collect()
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Lays out rows of cells as left-aligned columns under a header, separated by two spaces.
+pub fn format_table(header: &[&str], rows: &Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows {
+        for (idx, cell) in row.iter().enumerate() {
+            widths[idx] = widths[idx].max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let cells: Vec<String> = row.iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// The table cells for a result, given its recorded answer if any, and whether it counts as a failure.
+pub fn result_row(result: &JobResult, expected: Option<&str>) -> (Vec<String>, bool) {
+    let (answer, status, failed) = match (&result.outcome, expected) {
+        (Outcome::Answer(answer), Some(known)) if known == answer => (answer.clone(), "pass".to_string(), false),
+        (Outcome::Answer(answer), Some(known)) => (answer.clone(), format!("FAIL: expected {}", known), true),
+        (Outcome::Answer(answer), None) => (answer.clone(), "missing answer".to_string(), false),
+        (Outcome::Failed(err), _) => ("-".to_string(), format!("FAIL: {}", err), true),
+        (Outcome::Panicked(msg), _) => ("-".to_string(), format!("PANIC: {}", msg), true),
+    };
+    let row = vec![
+        result.day.to_string(),
+        result.part.to_string(),
+        answer,
+        format_ms(result.wall),
+        format_ms(result.cpu),
+        status,
+    ];
+    (row, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use aoc_common::Error;
+
+    use crate::days::PartResult;
+
+    fn answer_42(_text: &str, parts: &[u8], _params: &Params) -> Result<Vec<PartResult>, Error> {
+        Ok(parts.iter().map(|n| PartResult{ part: *n, answer: "42".to_string(), details: Default::default(), elapsed: Duration::ZERO, cached: false, certificate: None, counters: Default::default(), memory: None }).collect())
+    }
+
+    fn always_panics(_text: &str, _parts: &[u8], _params: &Params) -> Result<Vec<PartResult>, Error> {
+        panic!("out of cheese");
+    }
+
+    fn job(day: &'static str, part: u8, run: RunFn) -> Job {
+        Job{ day: day, part: part, source: InputSource::Inline(String::new()), run: run, timeout: None }
+    }
+
+    #[test]
+    fn test_run_jobs_keeps_order_and_catches_panics() {
+        let jobs = vec![job("1", 1, answer_42), job("2", 1, always_panics), job("3", 2, answer_42)];
+        let results = run_jobs(jobs, 2);
+        let summary: Vec<(&str, u8, bool)> = results.iter()
+            .map(|r| (r.day, r.part, matches!(r.outcome, Outcome::Panicked(_))))
+            .collect();
+        assert_eq!(summary, vec![("1", 1, false), ("2", 1, true), ("3", 2, false)]);
+
+        let (row, failed) = result_row(&results[1], Some("42"));
+        assert!(failed);
+        assert_eq!(row[5], "PANIC: out of cheese");
+        let (row, failed) = result_row(&results[2], Some("41"));
+        assert!(failed);
+        assert_eq!(row[5], "FAIL: expected 41");
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![vec!["10".to_string(), "2".to_string()], vec!["3".to_string(), "123".to_string()]];
+        assert_eq!(format_table(&["day", "x"], &rows), "day  x\n10   2\n3    123\n");
+    }
+}
@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+
+use aoc_common::{Progress, ProgressReporter};
+use crossterm::cursor::MoveToColumn;
+use crossterm::execute;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+use log::info;
+
+const BAR_WIDTH: usize = 24;
+// Quick solves finish before anything is shown, so they never flicker a bar or log a line.
+const BAR_INTERVAL: Duration = Duration::from_millis(100);
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shows a solve's progress on stderr: a bar with an ETA in a terminal, and a
+/// log line every few seconds otherwise.
+pub struct ProgressDisplay {
+    label: String,
+    terminal: bool,
+    state: RefCell<State>,
+}
+
+struct State {
+    unit: &'static str,
+    done: u64,
+    started: Instant,
+    shown: Option<Instant>,
+    drawn: bool,
+}
+
+impl ProgressDisplay {
+    pub fn new(label: String) -> ProgressDisplay {
+        let state = State{ unit: "", done: 0, started: Instant::now(), shown: None, drawn: false };
+        ProgressDisplay{ label: label, terminal: io::stderr().is_terminal(), state: RefCell::new(state) }
+    }
+
+    /// Clears the bar, if one is showing, so the answers print on a clean line.
+    pub fn finish(&self) {
+        let mut state = self.state.borrow_mut();
+        if state.drawn {
+            let _ = execute!(io::stderr(), MoveToColumn(0), Clear(ClearType::CurrentLine));
+            state.drawn = false;
+        }
+    }
+}
+
+impl ProgressReporter for ProgressDisplay {
+    fn report(&self, progress: &Progress) {
+        let now = Instant::now();
+        let mut state = self.state.borrow_mut();
+        // Each part counts from zero again, so its ETA starts over too.
+        if progress.unit != state.unit || progress.done < state.done {
+            state.unit = progress.unit;
+            state.started = now;
+            state.shown = None;
+        }
+        state.done = progress.done;
+
+        let interval = if self.terminal { BAR_INTERVAL } else { LOG_INTERVAL };
+        if now.duration_since(state.shown.unwrap_or(state.started)) < interval {
+            return;
+        }
+        state.shown = Some(now);
+        let elapsed = now.duration_since(state.started);
+        if self.terminal {
+            let line = bar_line(&self.label, progress, elapsed);
+            let _ = execute!(io::stderr(), MoveToColumn(0), Clear(ClearType::CurrentLine), Print(line));
+            state.drawn = true;
+        } else {
+            info!(target: "progress", "{}", log_line(&self.label, progress, elapsed));
+        }
+    }
+}
+
+/// How much longer the rest will take, going at the rate so far.
+fn eta(progress: &Progress, elapsed: Duration) -> Option<Duration> {
+    let total = progress.total?;
+    if progress.done == 0 {
+        return None;
+    }
+    let left = total.saturating_sub(progress.done);
+    Some(elapsed.mul_f64(left as f64 / progress.done as f64))
+}
+
+fn format_eta(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        return format!("{}s", secs);
+    }
+    if secs < 3600 {
+        return format!("{}m{:02}s", secs / 60, secs % 60);
+    }
+    format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+}
+
+fn percent(done: u64, total: u64) -> u64 {
+    if total == 0 {
+        return 100;
+    }
+    done.min(total) * 100 / total
+}
+
+// Without a total there is nothing to fill a bar or estimate from, only a count.
+fn count_line(label: &str, progress: &Progress, elapsed: Duration) -> String {
+    format!("{}: {} {} after {}", label, progress.done, progress.unit, format_eta(elapsed))
+}
+
+fn bar_line(label: &str, progress: &Progress, elapsed: Duration) -> String {
+    let total = match progress.total {
+        Some(total) => total,
+        None => return count_line(label, progress, elapsed),
+    };
+    let filled = percent(progress.done, total) as usize * BAR_WIDTH / 100;
+    let bar = format!("{}{}", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+    let eta = eta(progress, elapsed).map(format_eta).unwrap_or("?".to_string());
+    format!("{} [{}] {:>3}% {}/{} {}, ETA {}", label, bar, percent(progress.done, total), progress.done, total, progress.unit, eta)
+}
+
+fn log_line(label: &str, progress: &Progress, elapsed: Duration) -> String {
+    let total = match progress.total {
+        Some(total) => total,
+        None => return count_line(label, progress, elapsed),
+    };
+    let mut line = format!("{}: {} of {} {} ({}%) after {}", label, progress.done, total, progress.unit, percent(progress.done, total), format_eta(elapsed));
+    if let Some(eta) = eta(progress, elapsed) {
+        line = format!("{}, about {} left", line, format_eta(eta));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_line() {
+        let turns = Progress{ unit: "turns", done: 7_500_000, total: Some(30_000_000) };
+        assert_eq!(bar_line("day 15", &turns, Duration::from_secs(2)), "day 15 [######------------------]  25% 7500000/30000000 turns, ETA 6s");
+        let generations = Progress{ unit: "generations", done: 42, total: None };
+        assert_eq!(bar_line("day 11", &generations, Duration::from_millis(3500)), "day 11: 42 generations after 3s");
+    }
+
+    #[test]
+    fn test_log_line() {
+        let cycles = Progress{ unit: "cycles", done: 4, total: Some(6) };
+        assert_eq!(log_line("day 17", &cycles, Duration::from_secs(80)), "day 17: 4 of 6 cycles (66%) after 1m20s, about 40s left");
+        let started = Progress{ unit: "cycles", done: 0, total: Some(6) };
+        assert_eq!(log_line("day 17", &started, Duration::from_secs(5)), "day 17: 0 of 6 cycles (0%) after 5s");
+        assert_eq!(format_eta(Duration::from_secs(7384)), "2h03m");
+    }
+}
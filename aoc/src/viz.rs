@@ -0,0 +1,223 @@
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use aoc_common::Simulation;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+const MAX_FPS: u32 = 64;
+const HELP: &str = "space play/pause  n step  +/- speed  r restart  q quit";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    TogglePlay,
+    Step,
+    Faster,
+    Slower,
+    Restart,
+    Quit,
+    Nothing,
+}
+
+pub fn action_for(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        KeyCode::Char(' ') | KeyCode::Char('p') => Action::TogglePlay,
+        KeyCode::Char('n') | KeyCode::Char('.') | KeyCode::Right => Action::Step,
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => Action::Faster,
+        KeyCode::Char('-') | KeyCode::Down => Action::Slower,
+        KeyCode::Char('r') => Action::Restart,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => Action::Nothing,
+    }
+}
+
+/// Playback state: whether frames advance on their own, and how often.
+#[derive(Debug, PartialEq)]
+pub struct Player {
+    pub playing: bool,
+    pub fps: u32,
+    /// The simulation has no steps left.
+    pub finished: bool,
+}
+
+impl Player {
+    pub fn new(fps: u32) -> Player {
+        Player{ playing: false, fps: fps.clamp(1, MAX_FPS), finished: false }
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+
+    pub fn advance(&mut self, sim: &mut dyn Simulation) {
+        if self.finished || !sim.step() {
+            self.finished = true;
+            self.playing = false;
+        }
+    }
+
+    /// Carries out every action except restarting and quitting, which are up to the caller.
+    pub fn apply(&mut self, action: Action, sim: &mut dyn Simulation) {
+        match action {
+            Action::TogglePlay => self.playing = !self.playing && !self.finished,
+            Action::Step => {
+                self.playing = false;
+                self.advance(sim);
+            },
+            Action::Faster => self.fps = (self.fps * 2).min(MAX_FPS),
+            Action::Slower => self.fps = (self.fps / 2).max(1),
+            Action::Restart | Action::Quit | Action::Nothing => {},
+        }
+    }
+
+    fn describe(&self) -> String {
+        let state = if self.finished { "finished" } else if self.playing { "playing" } else { "paused" };
+        format!("{} at {} fps", state, self.fps)
+    }
+}
+
+fn draw(out: &mut impl Write, title: &str, sim: &dyn Simulation, player: &Player) -> io::Result<()> {
+    let (cols, rows) = terminal::size()?;
+    let header = [format!("{}: {}", title, player.describe()), sim.status(), String::new()];
+    let frame = sim.render();
+    let body = frame.lines().take((rows as usize).saturating_sub(header.len() + 1));
+
+    queue!(out, Clear(ClearType::All))?;
+    for (row, line) in header.iter().map(|ln| ln.as_str()).chain(body).enumerate() {
+        let line: String = line.chars().take(cols as usize).collect();
+        queue!(out, MoveTo(0, row as u16), Print(line))?;
+    }
+    queue!(out, MoveTo(0, rows.saturating_sub(1)), Print(HELP))?;
+    out.flush()
+}
+
+fn play_in_terminal<F>(title: &str, start: F, fps: u32) -> Result<(), String>
+where
+    F: Fn() -> Result<Box<dyn Simulation>, String>,
+{
+    let mut out = io::stdout();
+    let mut sim = start()?;
+    let mut player = Player::new(fps);
+    let mut next_frame = Instant::now();
+    loop {
+        draw(&mut out, title, &*sim, &player).map_err(|err| err.to_string())?;
+
+        let wait = if player.playing { next_frame.saturating_duration_since(Instant::now()) } else { Duration::from_secs(60) };
+        if !event::poll(wait).map_err(|err| err.to_string())? {
+            if player.playing {
+                player.advance(&mut *sim);
+                next_frame = Instant::now() + player.frame_time();
+            }
+            continue;
+        }
+
+        // Anything else, like a resize, just redraws.
+        let key = match event::read().map_err(|err| err.to_string())? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match action_for(&key) {
+            Action::Quit => return Ok(()),
+            Action::Restart => {
+                sim = start()?;
+                player = Player{ finished: false, ..player };
+            },
+            action => {
+                player.apply(action, &mut *sim);
+                next_frame = Instant::now() + player.frame_time();
+            },
+        }
+    }
+}
+
+/// Prints every frame one after another, for when there is no terminal to animate.
+fn print_frames(title: &str, sim: &mut dyn Simulation) {
+    println!("{}: {}\n{}", title, sim.status(), sim.render());
+    while sim.step() {
+        println!("{}: {}\n{}", title, sim.status(), sim.render());
+    }
+}
+
+/// Plays back the simulation made by `start` until the user quits. `start` is called
+/// again to restart from the beginning.
+pub fn play<F>(title: &str, start: F, fps: u32) -> Result<(), String>
+where
+    F: Fn() -> Result<Box<dyn Simulation>, String>,
+{
+    if !io::stdout().is_terminal() {
+        print_frames(title, &mut *start()?);
+        return Ok(());
+    }
+
+    let mut out = io::stdout();
+    terminal::enable_raw_mode().map_err(|err| err.to_string())?;
+    let shown = execute!(out, EnterAlternateScreen, Hide).map_err(|err| err.to_string());
+    let result = shown.and_then(|_| play_in_terminal(title, start, fps));
+    let _ = execute!(out, Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+            self.0 = self.0 - 1;
+            return true;
+        }
+
+        fn render(&self) -> String {
+            format!("{}\n", self.0)
+        }
+
+        fn status(&self) -> String {
+            format!("{} left", self.0)
+        }
+    }
+
+    #[test]
+    fn test_keys() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(action_for(&key(KeyCode::Char(' '))), Action::TogglePlay);
+        assert_eq!(action_for(&key(KeyCode::Right)), Action::Step);
+        assert_eq!(action_for(&key(KeyCode::Esc)), Action::Quit);
+        assert_eq!(action_for(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)), Action::Quit);
+        assert_eq!(action_for(&key(KeyCode::Char('x'))), Action::Nothing);
+    }
+
+    #[test]
+    fn test_player() {
+        let mut sim = Countdown(2);
+        let mut player = Player::new(100);
+        assert_eq!(player.fps, MAX_FPS);
+
+        player.apply(Action::TogglePlay, &mut sim);
+        assert!(player.playing);
+        player.apply(Action::Step, &mut sim);
+        assert_eq!((player.playing, sim.0), (false, 1));
+
+        player.apply(Action::Slower, &mut sim);
+        player.apply(Action::Faster, &mut sim);
+        player.apply(Action::Faster, &mut sim);
+        assert_eq!(player.fps, MAX_FPS);
+        assert_eq!(player.frame_time(), Duration::from_micros(15625));
+
+        player.apply(Action::TogglePlay, &mut sim);
+        player.advance(&mut sim);
+        player.advance(&mut sim);
+        assert_eq!(player, Player{ playing: false, fps: MAX_FPS, finished: true });
+        player.apply(Action::TogglePlay, &mut sim);
+        assert!(!player.playing);
+    }
+}
@@ -0,0 +1,52 @@
+use std::any::type_name;
+
+use num::traits::{CheckedAdd, CheckedMul, One};
+
+use crate::Error;
+
+fn overflow<N>(what: &str) -> Error {
+    Error::Overflow(format!("{} does not fit in {}", what, type_name::<N>()))
+}
+
+/// `a + b`, or an overflow error saying `what` was being worked out.
+pub fn checked_add<N: CheckedAdd>(a: &N, b: &N, what: &str) -> Result<N, Error> {
+    a.checked_add(b).ok_or_else(|| overflow::<N>(what))
+}
+
+/// `a * b`, or an overflow error saying `what` was being worked out.
+pub fn checked_mul<N: CheckedMul>(a: &N, b: &N, what: &str) -> Result<N, Error> {
+    a.checked_mul(b).ok_or_else(|| overflow::<N>(what))
+}
+
+/// The product of `values`, or an overflow error as soon as it stops fitting in `N`.
+pub fn checked_product<N, I>(values: I, what: &str) -> Result<N, Error>
+where
+    N: CheckedMul + One,
+    I: IntoIterator<Item = N>,
+{
+    let mut accum = N::one();
+    for value in values {
+        accum = checked_mul(&accum, &value, what)?;
+    }
+    Ok(accum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigUint;
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        assert_eq!(checked_add(&1_u32, &2, "a sum").unwrap(), 3);
+        let err = checked_mul(&u32::MAX, &2, "a product").unwrap_err();
+        assert_eq!(err.to_string(), "arithmetic overflow: a product does not fit in u32");
+        assert!(checked_product(vec![1_i32 << 16, 1 << 16], "a product").is_err());
+    }
+
+    #[test]
+    fn test_big_integers_do_not_overflow() {
+        let product: BigUint = checked_product(vec![BigUint::from(u64::MAX); 3], "a product").unwrap();
+        assert_eq!(product.bits(), 192);
+    }
+}
@@ -0,0 +1,156 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{report_progress, Error, Progress};
+
+/// Tells long-running solvers when to give up. Clones share one flag, so another
+/// thread can cancel a solve in progress.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    /// A token that cancels itself once `timeout` has passed.
+    pub fn with_timeout(timeout: Duration) -> CancelToken {
+        CancelToken{ cancelled: Arc::new(AtomicBool::new(false)), deadline: Some(Instant::now() + timeout) }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Runs `solve` with this as the token that `Iterations` on this thread check.
+    pub fn run<T, F: FnOnce() -> T>(&self, solve: F) -> T {
+        struct Restore(Option<CancelToken>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                let _ = CURRENT.try_with(|current| current.replace(previous));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        solve()
+    }
+}
+
+/// Counts the iterations of a loop that could run for a long time, failing once
+/// the solve it is part of is cancelled. Loops outside `CancelToken::run` never fail.
+#[derive(Debug)]
+pub struct Iterations {
+    count: u64,
+    check_every: u64,
+    token: Option<CancelToken>,
+    progress: Option<(&'static str, Option<u64>)>,
+}
+
+impl Iterations {
+    /// For loops whose iterations each take a while, checked every time.
+    pub fn new() -> Iterations {
+        Iterations::checking_every(1)
+    }
+
+    /// For tight loops, where looking at the clock every time would slow them down.
+    pub fn checking_every(n: u64) -> Iterations {
+        let token = CURRENT.with(|current| current.borrow().clone());
+        Iterations{ count: 0, check_every: n.max(1), token: token, progress: None }
+    }
+
+    /// Also reports progress each time it checks for cancellation, counting
+    /// iterations as `unit`, e.g. `turns`, out of `total` if that is known.
+    pub fn reporting(self, unit: &'static str, total: Option<u64>) -> Iterations {
+        Iterations{ progress: Some((unit, total)), ..self }
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Counts another iteration.
+    pub fn tick(&mut self) -> Result<(), Error> {
+        self.count = self.count + 1;
+        if !self.count.is_multiple_of(self.check_every) {
+            return Ok(());
+        }
+        if let Some((unit, total)) = self.progress {
+            report_progress(&Progress{ unit: unit, done: self.count, total: total });
+        }
+        if self.token.as_ref().is_some_and(|token| token.is_cancelled()) {
+            return Err(Error::TimedOut(self.count));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Iterations {
+    fn default() -> Iterations {
+        Iterations::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spin(limit: u64) -> Result<u64, Error> {
+        let mut iterations = Iterations::checking_every(10);
+        while iterations.count() < limit {
+            iterations.tick()?;
+        }
+        Ok(iterations.count())
+    }
+
+    #[test]
+    fn test_cancellation() {
+        assert_eq!(spin(25).unwrap(), 25);
+
+        let token = CancelToken::new();
+        assert_eq!(token.run(|| spin(25)).unwrap(), 25);
+        token.cancel();
+        let err = token.run(|| spin(25)).unwrap_err();
+        assert_eq!(err.to_string(), "timed out after 10 iterations");
+        assert_eq!(spin(25).unwrap(), 25);
+
+        let expired = CancelToken::with_timeout(Duration::ZERO);
+        assert!(matches!(expired.run(|| spin(u64::MAX)), Err(Error::TimedOut(10))));
+        assert!(!CancelToken::with_timeout(Duration::from_secs(60)).is_cancelled());
+    }
+
+    struct Recorder(RefCell<Vec<u64>>);
+
+    impl crate::ProgressReporter for Recorder {
+        fn report(&self, progress: &Progress) {
+            assert_eq!((progress.unit, progress.total), ("turns", Some(25)));
+            self.0.borrow_mut().push(progress.done);
+        }
+    }
+
+    #[test]
+    fn test_reporting() {
+        let recorder = std::rc::Rc::new(Recorder(RefCell::new(Vec::new())));
+        crate::with_progress(recorder.clone(), || {
+            let mut iterations = Iterations::checking_every(10).reporting("turns", Some(25));
+            for _ in 0..25 {
+                iterations.tick().unwrap();
+            }
+        });
+        assert_eq!(*recorder.0.borrow(), vec![10, 20]);
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::Error;
+
+/// Evidence for an answer, e.g. the entries that sum to 2020, which a day's
+/// `Solution::check` can confirm against the input without solving the part again.
+pub type Certificate = Value;
+
+/// An answer together with the certificate for it, both from the same solve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Certified<A> {
+    pub answer: A,
+    pub certificate: Certificate,
+}
+
+impl<A> Certified<A> {
+    pub fn new(answer: A, certificate: Certificate) -> Certified<A> {
+        Certified{ answer: answer, certificate: certificate }
+    }
+
+    /// An answer without a certificate, for parts where checking any evidence
+    /// small enough to keep would cost as much as solving again.
+    pub fn unchecked(answer: A) -> Certified<A> {
+        Certified{ answer: answer, certificate: Certificate::Null }
+    }
+}
+
+/// The field `name` of a certificate, rejecting the certificate when it is missing or mistyped.
+pub fn certificate_field<T: DeserializeOwned>(certificate: &Certificate, name: &str) -> Result<T, Error> {
+    match certificate.get(name) {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|err| Error::Certificate(format!("`{}` is malformed: {}", name, err))),
+        None => Err(Error::Certificate(format!("`{}` is missing", name))),
+    }
+}
+
+/// The field `name` as a set of indices into something `len` long, e.g. the
+/// valid lines, rejecting repeated and out of range indices.
+pub fn certificate_indices(certificate: &Certificate, name: &str, len: usize) -> Result<BTreeSet<usize>, Error> {
+    let mut indices = BTreeSet::new();
+    for idx in certificate_field::<Vec<usize>>(certificate, name)? {
+        if idx >= len {
+            return Err(Error::Certificate(format!("`{}` lists {}, but there are only {}", name, idx, len)));
+        }
+        if !indices.insert(idx) {
+            return Err(Error::Certificate(format!("`{}` lists {} twice", name, idx)));
+        }
+    }
+    Ok(indices)
+}
+
+/// Rejects a certificate that proves `proven` when the answer given was `answer`.
+pub fn check_answer<T: fmt::Display>(proven: T, answer: &str) -> Result<(), Error> {
+    let proven = proven.to_string();
+    if proven != answer {
+        return Err(Error::Certificate(format!("it proves {}, not {}", proven, answer)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_certificate_field() {
+        let certificate = json!({ "entries": [1721, 299], "flipped": "x" });
+        assert_eq!(certificate_field::<Vec<i32>>(&certificate, "entries").unwrap(), vec![1721, 299]);
+        let err = certificate_field::<usize>(&certificate, "flipped").unwrap_err();
+        assert!(err.to_string().starts_with("certificate rejected: `flipped` is malformed: "));
+        assert_eq!(certificate_field::<usize>(&certificate, "index").unwrap_err().to_string(), "certificate rejected: `index` is missing");
+    }
+
+    #[test]
+    fn test_certificate_indices() {
+        let certificate = json!({ "valid": [3, 0], "twice": [1, 1] });
+        assert_eq!(certificate_indices(&certificate, "valid", 4).unwrap(), BTreeSet::from([0, 3]));
+        assert_eq!(certificate_indices(&certificate, "valid", 3).unwrap_err().to_string(), "certificate rejected: `valid` lists 3, but there are only 3");
+        assert_eq!(certificate_indices(&certificate, "twice", 3).unwrap_err().to_string(), "certificate rejected: `twice` lists 1 twice");
+        assert!(check_answer(12, "12").is_ok());
+        assert_eq!(check_answer(12, "13").unwrap_err().to_string(), "certificate rejected: it proves 12, not 13");
+    }
+}
@@ -0,0 +1,236 @@
+//! Parser combinators for puzzle lines, built on `nom`. Parsers report what
+//! they expected and where, which `parse_line` turns into a `ParseError`.
+
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use nom::error::ErrorKind;
+use nom::{Err, IResult};
+
+pub use nom::branch::alt;
+pub use nom::bytes::complete::{take_till, take_till1, take_while, take_while1, take_while_m_n};
+pub use nom::character::complete::{alpha1, anychar, char, digit1, one_of, space0, space1};
+pub use nom::combinator::{consumed, cut, map, opt, recognize, value};
+pub use nom::multi::{many0, separated_list1};
+pub use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+pub use nom::Parser;
+
+use crate::ParseError;
+
+/// What a parser wanted, and the rest of the line from where it gave up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expected<'a> {
+    pub at: &'a str,
+    pub expected: Cow<'static, str>,
+}
+
+impl<'a> Expected<'a> {
+    pub fn new<S: Into<Cow<'static, str>>>(at: &'a str, expected: S) -> Expected<'a> {
+        Expected{ at: at, expected: expected.into() }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Expected<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Expected<'a> {
+        let expected = match kind {
+            ErrorKind::Digit => "a digit",
+            ErrorKind::Alpha => "a letter",
+            ErrorKind::Space => "a space",
+            ErrorKind::Eof => "the end of the line",
+            _ => "something else",
+        };
+        Expected::new(input, expected)
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Expected<'a>) -> Expected<'a> {
+        other
+    }
+
+    fn from_char(input: &'a str, ch: char) -> Expected<'a> {
+        Expected::new(input, format!("`{}`", ch))
+    }
+
+    // Of two failed alternatives, the one that got further along the line knows more.
+    fn or(self, other: Expected<'a>) -> Expected<'a> {
+        if other.at.len() < self.at.len() { other } else { self }
+    }
+}
+
+pub type PResult<'a, T> = IResult<&'a str, T, Expected<'a>>;
+
+/// Runs `parser` over the whole of `ln`, reporting where it stopped and what it expected there.
+pub fn parse_line<'a, T, P>(ln: &'a str, mut parser: P) -> Result<T, ParseError>
+where
+    P: Parser<&'a str, T, Expected<'a>>,
+{
+    let failure = match parser.parse(ln) {
+        Ok(("", value)) => return Ok(value),
+        Ok((rest, _)) => Expected::new(rest, "the end of the line"),
+        Err(Err::Error(failure)) | Err(Err::Failure(failure)) => failure,
+        Err(Err::Incomplete(_)) => Expected::new(&ln[ln.len()..], "more of the line"),
+    };
+    Err(ParseError::at(ln, ln.len() - failure.at.len(), &failure.expected))
+}
+
+/// What `parser` makes of the whole of `text`, for when only success matters.
+pub fn parse_all<'a, T, P>(text: &'a str, mut parser: P) -> Option<T>
+where
+    P: Parser<&'a str, T, Expected<'a>>,
+{
+    match parser.parse(text) {
+        Ok(("", value)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Describes a failure of `parser` where it started as `expected`. Failures further
+/// along are kept, as they say more precisely what went wrong.
+pub fn expect<'a, T, P>(expected: &'static str, mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, T>
+where
+    P: Parser<&'a str, T, Expected<'a>>,
+{
+    move |input: &'a str| {
+        parser.parse(input).map_err(|err| err.map(|failure| {
+            if failure.at.len() == input.len() { Expected::new(input, expected) } else { failure }
+        }))
+    }
+}
+
+/// Exactly `text`.
+pub fn literal<'a>(text: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(text) {
+        Some(rest) => Ok((rest, &input[..text.len()])),
+        None => Err(Err::Error(Expected::new(input, format!("`{}`", text)))),
+    }
+}
+
+/// Succeeds only with nothing left of the line.
+pub fn end_of_line<'a>() -> impl FnMut(&'a str) -> PResult<'a, ()> {
+    move |input: &'a str| match input {
+        "" => Ok((input, ())),
+        _ => Err(Err::Error(Expected::new(input, "the end of the line"))),
+    }
+}
+
+/// Digits read as a `T`. A number too large for a `T` fails at its first digit.
+pub fn unsigned<'a, T: FromStr>() -> impl FnMut(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| {
+        let (rest, digits) = digit1(input)?;
+        match digits.parse() {
+            Ok(n) => Ok((rest, n)),
+            Err(_) => Err(Err::Failure(Expected::new(input, "a smaller number"))),
+        }
+    }
+}
+
+/// Digits after an optional `+` or `-`, read as a `T`.
+pub fn signed<'a, T: FromStr>() -> impl FnMut(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| {
+        let (rest, number) = recognize(pair(opt(one_of("+-")), digit1))(input)?;
+        match number.parse() {
+            Ok(n) => Ok((rest, n)),
+            Err(_) => Err(Err::Failure(Expected::new(input, "a smaller number"))),
+        }
+    }
+}
+
+/// Two numbers joined by `-`, like `1-3`.
+pub fn range<'a, T: FromStr>() -> impl FnMut(&'a str) -> PResult<'a, (T, T)> {
+    separated_pair(unsigned(), char('-'), unsigned())
+}
+
+/// A token like `key:value` split at the first `sep`. The key may not be empty,
+/// and neither part runs past whitespace.
+pub fn key_value<'a>(sep: char) -> impl FnMut(&'a str) -> PResult<'a, (&'a str, &'a str)> {
+    separated_pair(
+        expect("a key", take_till1(move |c: char| c == sep || c.is_whitespace())),
+        char(sep),
+        take_till(|c: char| c.is_whitespace()),
+    )
+}
+
+/// Every token of a line, parsed with `parser`. Tokens are separated by spaces or
+/// tabs, and the first one `parser` rejects is the error.
+pub fn tokens<'a, T, P>(mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<T>>
+where
+    P: Parser<&'a str, T, Expected<'a>>,
+{
+    move |input: &'a str| {
+        let mut items = Vec::new();
+        let (mut rest, _) = space0(input)?;
+        while !rest.is_empty() {
+            let (after, item) = parser.parse(rest)?;
+            let (after, gap) = space0(after)?;
+            if gap.is_empty() && !after.is_empty() {
+                return Err(Err::Error(Expected::new(after, "a space")));
+            }
+            items.push(item);
+            rest = after;
+        }
+        Ok((rest, items))
+    }
+}
+
+/// Fails with `expected` where `parser` started unless `check` accepts what it parsed.
+pub fn verified<'a, T, P, F>(expected: &'static str, mut parser: P, check: F) -> impl FnMut(&'a str) -> PResult<'a, T>
+where
+    P: Parser<&'a str, T, Expected<'a>>,
+    F: Fn(&T) -> bool,
+{
+    move |input: &'a str| {
+        let (rest, parsed) = parser.parse(input)?;
+        if !check(&parsed) {
+            return Err(Err::Error(Expected::new(input, expected)));
+        }
+        Ok((rest, parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_and_ranges() {
+        assert_eq!(parse_line("1-3", range::<u8>()), Ok((1, 3)));
+        assert_eq!(parse_line("-12", signed::<i32>()), Ok(-12));
+
+        let err = parse_line("1-3x", range::<u8>()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (4, "the end of the line"));
+        let err = parse_line("1-x", range::<u8>()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str(), err.found), (3, "a digit", Some("x".to_string())));
+        let err = parse_line("300-1", range::<u8>()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "a smaller number"));
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(parse_line(" ecl:gry  pid: ", tokens(key_value(':'))), Ok(vec![("ecl", "gry"), ("pid", "")]));
+        assert_eq!(parse_line("", tokens(key_value(':'))), Ok(vec![]));
+        let err = parse_line("ecl:gry :x", tokens(key_value(':'))).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (9, "a key"));
+        let err = parse_line("ecl:gry hgt", tokens(key_value(':'))).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (12, "`:`"));
+        let err = parse_line(":x", key_value(':')).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "a key"));
+    }
+
+    #[test]
+    fn test_alternatives_report_the_furthest_failure() {
+        let instr = || alt((
+            preceded(literal("mask = "), map(take_while1(|c: char| c == 'X'), |_| 0)),
+            preceded(literal("mem["), terminated(unsigned::<u32>(), char(']'))),
+        ));
+        assert_eq!(parse_line("mem[8]", instr()), Ok(8));
+        let err = parse_line("mem[8)", instr()).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (6, "`]`"));
+        let err = parse_line("mov", expect("`mask` or `mem`", instr())).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (1, "`mask` or `mem`"));
+
+        let color = || preceded(char('#'), take_while_m_n(6, 6, |c: char| c.is_ascii_hexdigit()));
+        assert_eq!(parse_all("#a0b1c2", color()), Some("a0b1c2"));
+        assert_eq!(parse_all("#a0b1c2d", color()), None);
+        let even = || verified("an even number", unsigned::<u32>(), |n| n % 2 == 0);
+        assert_eq!(parse_line("3", even()).unwrap_err().expected, "an even number");
+    }
+}
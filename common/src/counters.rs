@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A count of some operation a solver carried out, either in total or round by round.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Counter {
+    Total(u64),
+    PerRound(Vec<u64>),
+}
+
+/// Operation counts from solving one part, by name.
+pub type Counters = BTreeMap<String, Counter>;
+
+thread_local! {
+    static COUNTERS: RefCell<Counters> = const { RefCell::new(Counters::new()) };
+}
+
+/// Adds `n` to the counter `name`.
+pub fn count(name: &str, n: u64) {
+    COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        match counters.get_mut(name) {
+            Some(Counter::Total(total)) => *total = *total + n,
+            _ => { counters.insert(name.to_string(), Counter::Total(n)); },
+        }
+    });
+}
+
+/// Records `n` as the counter `name` for the latest round, e.g. a generation.
+pub fn count_round(name: &str, n: u64) {
+    COUNTERS.with(|counters| {
+        let mut counters = counters.borrow_mut();
+        match counters.get_mut(name) {
+            Some(Counter::PerRound(rounds)) => rounds.push(n),
+            _ => { counters.insert(name.to_string(), Counter::PerRound(vec![n])); },
+        }
+    });
+}
+
+/// Everything counted on this thread since the last call, which starts them over.
+pub fn take_counters() -> Counters {
+    COUNTERS.with(|counters| counters.replace(Counters::new()))
+}
+
+impl fmt::Display for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Counter::Total(total) => write!(f, "{}", total),
+            Counter::PerRound(rounds) => {
+                let total: u64 = rounds.iter().sum();
+                let most = rounds.iter().max().unwrap_or(&0);
+                write!(f, "{} over {} rounds, at most {} in one", total, rounds.len(), most)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        take_counters();
+        count("steps", 2);
+        count("steps", 3);
+        count_round("cells", 4);
+        count_round("cells", 1);
+
+        let counters = take_counters();
+        assert_eq!(counters.get("steps"), Some(&Counter::Total(5)));
+        assert_eq!(counters["cells"].to_string(), "5 over 2 rounds, at most 4 in one");
+        assert!(take_counters().is_empty());
+
+        let json = serde_json::to_string(&counters).unwrap();
+        assert_eq!(json, r#"{"cells":[4,1],"steps":5}"#);
+        assert_eq!(serde_json::from_str::<Counters>(&json).unwrap(), counters);
+    }
+}
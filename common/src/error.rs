@@ -0,0 +1,170 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input could not be read.
+    Io(String),
+    /// The puzzle input could not be parsed.
+    Parse(ParseError),
+    /// The input was parsed but does not have an answer, e.g. no entries sum to 2020.
+    NoAnswer(String),
+    /// The part has not been solved for this day.
+    Unsolved,
+    /// An answer grew too large for its integer type. Days built with their
+    /// `bigint` feature work in arbitrary precision instead.
+    Overflow(String),
+    /// A puzzle parameter was unknown or its value could not be used.
+    Param(String),
+    /// A certificate did not prove its answer.
+    Certificate(String),
+    /// The solve was cancelled, e.g. by running out of time, after this many iterations.
+    TimedOut(u64),
+}
+
+impl Error {
+    /// Records the file the input came from on parse errors.
+    pub fn in_file(self, file: &str) -> Error {
+        match self {
+            Error::Parse(err) => Error::Parse(err.in_file(file)),
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(msg) => write!(f, "cannot read input: {}", msg),
+            Error::Parse(err) => write!(f, "invalid input: {}", err),
+            Error::NoAnswer(msg) => write!(f, "no answer: {}", msg),
+            Error::Unsolved => write!(f, "not solved"),
+            Error::Overflow(msg) => write!(f, "arithmetic overflow: {}", msg),
+            Error::Param(msg) => write!(f, "invalid parameter: {}", msg),
+            Error::Certificate(msg) => write!(f, "certificate rejected: {}", msg),
+            Error::TimedOut(iterations) => write!(f, "timed out after {} iterations", iterations),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        Error::Parse(err)
+    }
+}
+
+/// Where and why parsing stopped. Lines and columns count from 1, columns in chars.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The token found instead, or `None` at the end of a line.
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `text`. The token found there runs up to the next whitespace.
+    pub fn at(text: &str, offset: usize, expected: &str) -> ParseError {
+        let mut offset = offset.min(text.len());
+        // An offset inside a multi-byte char points at that char.
+        while !text.is_char_boundary(offset) {
+            offset = offset - 1;
+        }
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let rest = &text[offset..];
+        let mut token: String = rest.chars().take_while(|c| !c.is_whitespace()).collect();
+        if token.is_empty() {
+            // A stray space or tab is reported as itself rather than as the end of the line.
+            token = rest.chars().take(1).filter(|c| *c != '\n' && *c != '\r').collect();
+        }
+
+        ParseError{
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.to_string(),
+            found: if token.is_empty() { None } else { Some(token) },
+        }
+    }
+
+    /// An error at the start of `text`, e.g. when the whole line is unrecognized.
+    pub fn at_start(text: &str, expected: &str) -> ParseError {
+        return ParseError::at(text, 0, expected);
+    }
+
+    /// An error at `token`, which must be a slice of `text`.
+    pub fn at_token(text: &str, token: &str, expected: &str) -> ParseError {
+        let offset = (token.as_ptr() as usize).saturating_sub(text.as_ptr() as usize);
+        let mut err = ParseError::at(text, offset, expected);
+        if !token.is_empty() {
+            err.found = Some(token.to_string());
+        }
+        return err;
+    }
+
+    /// Moves an error found in a fragment of a larger text, where the fragment starts on `line`.
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        self.line = self.line + line - 1;
+        return self;
+    }
+
+    pub fn in_file(mut self, file: &str) -> ParseError {
+        self.file = Some(file.to_string());
+        return self;
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        write!(f, "{}:{}:{}: expected {}, ", file, self.line, self.column, self.expected)?;
+        match &self.found {
+            Some(token) => write!(f, "found {:?}", token),
+            None => write!(f, "found end of line"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_offset() {
+        let err = ParseError::at("abc\nde fg\n", 7, "a number");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 4);
+        assert_eq!(err.found, Some("fg".to_string()));
+    }
+
+    #[test]
+    fn test_at_end_of_line() {
+        let err = ParseError::at("mask = ", 7, "a bitmask");
+        assert_eq!(err.column, 8);
+        assert_eq!(err.found, None);
+        assert_eq!(err.to_string(), "<input>:1:8: expected a bitmask, found end of line");
+    }
+
+    #[test]
+    fn test_at_trailing_space() {
+        let err = ParseError::at("F10 ", 3, "end of line");
+        assert_eq!(err.column, 4);
+        assert_eq!(err.found, Some(" ".to_string()));
+    }
+
+    #[test]
+    fn test_at_inside_char() {
+        let err = ParseError::at("nop +0\nacc é9 x", 12, "a signed number");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 5);
+        assert_eq!(err.found, Some("é9".to_string()));
+        assert_eq!(ParseError::at("ü", 1, "a letter").column, 1);
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::ParseError;
+
+/// A grid cell that is read from and drawn as a single character.
+pub trait Tile: Sized {
+    /// The characters a cell can be, for parse errors, e.g. "`.` or `#`".
+    const EXPECTED: &'static str;
+
+    fn from_char(ch: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+/// Offsets to a cell's eight neighbors as `(dx, dy)`, with y growing downwards.
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0),           (1, 0),
+    (-1, 1),  (0, 1),  (1, 1),
+];
+
+/// A rectangular grid of cells addressed by `(x, y)` from the top left.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A grid with every cell set to `cell`.
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T> {
+        Grid{ width: width, height: height, cells: vec![cell; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `(x, y)`, or `None` outside the grid.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some(&self.cells[y as usize * self.width + x as usize])
+    }
+
+    /// The cell at `(x, y)` on a grid that repeats forever in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize);
+        let y = y.rem_euclid(self.height as isize);
+        &self.cells[y as usize * self.width + x as usize]
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        self.cells.iter().enumerate().map(move |(idx, cell)| (idx % self.width, idx / self.width, cell))
+    }
+
+    /// The cells around `(x, y)` with their positions, leaving out any beyond the edges.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        DIRECTIONS.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            self.get(nx, ny).map(|cell| (nx as usize, ny as usize, cell))
+        })
+    }
+
+    /// The cells seen looking from `(x, y)` in direction `(dx, dy)`, nearest first, up to the edge.
+    pub fn ray(&self, x: usize, y: usize, dx: isize, dy: isize) -> impl Iterator<Item = &T> + '_ {
+        assert!(dx != 0 || dy != 0, "A ray needs a direction.");
+        (1..).map_while(move |step| self.get(x as isize + dx * step, y as isize + dy * step))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid{ width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Tile> Grid<T> {
+    /// Reads one row per line. Every row must be as wide as the first, which must not be empty.
+    pub fn parse(text: &str) -> Result<Grid<T>, ParseError> {
+        let first = text.lines().next();
+        if first == Some("") {
+            return Err(ParseError::at("", 0, T::EXPECTED));
+        }
+        let width = first.map(|ln| ln.chars().count()).unwrap_or(0);
+        let mut cells: Vec<T> = Vec::new();
+        let mut height = 0;
+        let row_expected = format!("a row {} cells wide", width);
+        for (idx, ln) in text.lines().enumerate() {
+            let mut row_width = 0;
+            for (offset, ch) in ln.char_indices() {
+                if row_width == width {
+                    return Err(ParseError::at(ln, offset, &row_expected).on_line(idx + 1));
+                }
+                match T::from_char(ch) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(ParseError::at(ln, offset, T::EXPECTED).on_line(idx + 1)),
+                }
+                row_width = row_width + 1;
+            }
+            if row_width < width {
+                return Err(ParseError::at(ln, ln.len(), &row_expected).on_line(idx + 1));
+            }
+            height = height + 1;
+        }
+        Ok(Grid{ width: width, height: height, cells: cells })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "x = {} is outside a grid {} wide.", x, self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "x = {} is outside a grid {} wide.", x, self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Draws the grid as it would be parsed, one line per row.
+impl<T: Tile> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: String = row.iter().map(|cell| cell.to_char()).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    enum Square {
+        Open,
+        Tree,
+    }
+
+    impl Tile for Square {
+        const EXPECTED: &'static str = "`.` or `#`";
+
+        fn from_char(ch: char) -> Option<Square> {
+            match ch {
+                '.' => Some(Square::Open),
+                '#' => Some(Square::Tree),
+                _ => None,
+            }
+        }
+
+        fn to_char(&self) -> char {
+            if *self == Square::Tree { '#' } else { '.' }
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid: Grid<Square> = Grid::parse("..#\n#..\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], Square::Tree);
+        assert_eq!(grid.to_string(), "..#\n#..\n");
+
+        let err = Grid::<Square>::parse("..#\n#.x\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 3, "`.` or `#`"));
+        let err = Grid::<Square>::parse("..#\n#...\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 4, "a row 3 cells wide"));
+        let err = Grid::<Square>::parse("..#\n#.\n").unwrap_err();
+        assert_eq!((err.column, err.found), (3, None));
+        let err = Grid::<Square>::parse("\n\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.found), (1, 1, None));
+    }
+
+    #[test]
+    fn test_indexing() {
+        let mut blank = Grid::new(3, 2, Square::Open);
+        blank[(2, 0)] = Square::Tree;
+        blank[(0, 1)] = Square::Tree;
+        let grid: Grid<Square> = Grid::parse("..#\n#..\n").unwrap();
+        assert_eq!(blank, grid);
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_wrapping(5, 0), &Square::Tree);
+        assert_eq!(grid.get_wrapping(-3, -1), &Square::Tree);
+    }
+
+    #[test]
+    fn test_neighbors_and_rays() {
+        let grid: Grid<Square> = Grid::parse("#..\n...\n..#\n").unwrap();
+        assert_eq!(grid.neighbors(0, 0).count(), 3);
+        assert_eq!(grid.neighbors(1, 1).filter(|(_, _, cell)| **cell == Square::Tree).count(), 2);
+
+        let diagonal: Vec<&Square> = grid.ray(0, 0, 1, 1).collect();
+        assert_eq!(diagonal, vec![&Square::Open, &Square::Tree]);
+        assert_eq!(grid.ray(0, 0, -1, 0).count(), 0);
+    }
+}
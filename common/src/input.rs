@@ -0,0 +1,173 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+use crate::{to_lines, Error, ParseError};
+
+/// Where a puzzle input comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// Input given directly, e.g. on the command line.
+    Inline(String),
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            return InputSource::Stdin;
+        }
+        return InputSource::File(PathBuf::from(arg));
+    }
+
+    /// The input named on a day's command line: a path, `-` for stdin or `--inline TEXT`.
+    /// Without arguments it is the `default` file. Exits with a usage message on anything else.
+    pub fn from_args(default: &str) -> InputSource {
+        let args: Vec<String> = env::args().skip(1).collect();
+        match args.iter().map(|s| s.as_str()).collect::<Vec<&str>>().as_slice() {
+            [] => InputSource::from_arg(default),
+            ["--inline", text] => InputSource::Inline(text.to_string()),
+            [arg] if !arg.starts_with("--") => InputSource::from_arg(arg),
+            _ => {
+                let prog = env::args().next().unwrap_or_default();
+                eprintln!("usage: {} [PATH | - | --inline TEXT]", prog);
+                process::exit(2);
+            },
+        }
+    }
+
+    /// Reads the whole input. Stdin can only be read once.
+    pub fn read(&self) -> Result<String, Error> {
+        let io_error = |err: io::Error| Error::Io(format!("{}: {}", self, err));
+        match self {
+            InputSource::File(path) => fs::read_to_string(path).map_err(io_error),
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text).map_err(io_error)?;
+                Ok(text)
+            },
+            InputSource::Inline(text) => Ok(text.clone()),
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    fn from(arg: &str) -> InputSource {
+        InputSource::from_arg(arg)
+    }
+}
+
+/// The name used for the input in error messages.
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+/// Parses each line of `text` with `parse_line`, placing any error on the line it came from.
+pub fn parse_lines<T, F>(text: &str, mut parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    text.lines()
+        .enumerate()
+        .map(|(idx, ln)| parse_line(ln).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+/// Parses `token`, a slice of `ln`, as an integer. Errors point at the first
+/// character that is not a digit, or at the token itself if it overflows.
+pub fn parse_integer<T: FromStr>(ln: &str, token: &str) -> Result<T, ParseError> {
+    if let Ok(n) = token.parse::<T>() {
+        return Ok(n);
+    }
+
+    let digits = token.strip_prefix(|c| c == '+' || c == '-').unwrap_or(token);
+    let sign_len = token.len() - digits.len();
+    match digits.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        Some((idx, _)) => {
+            let bad = &token[(sign_len + idx)..];
+            let mut err = ParseError::at_token(ln, bad, "a digit");
+            err.found = bad.chars().next().map(|ch| ch.to_string());
+            Err(err)
+        },
+        None if digits.is_empty() => Err(ParseError::at_token(ln, &token[token.len()..], "a digit")),
+        None => Err(ParseError::at_token(ln, token, "a smaller number")),
+    }
+}
+
+/// Reads the whole of `source`, e.g. a path or `-` for stdin.
+pub fn read_input<S: Into<InputSource>>(source: S) -> Result<String, Error> {
+    source.into().read()
+}
+
+/// Every line of `source` as an owned string.
+pub fn read_lines<S: Into<InputSource>>(source: S) -> Result<Vec<String>, Error> {
+    Ok(to_lines(&read_input(source)?))
+}
+
+/// Reads `source` and parses it with `parse`, naming the source on parse errors.
+pub fn read_parsed<S, T, F>(source: S, parse: F) -> Result<T, Error>
+where
+    S: Into<InputSource>,
+    F: FnOnce(&str) -> Result<T, ParseError>,
+{
+    let source = source.into();
+    let contents = source.read()?;
+    Ok(parse(&contents).map_err(|err| err.in_file(&source.to_string()))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_source() {
+        assert_eq!(InputSource::from("-"), InputSource::Stdin);
+        assert_eq!(InputSource::from("input").to_string(), "input");
+
+        let source = InputSource::Inline("0,3,6".to_string());
+        assert_eq!(source.read().unwrap(), "0,3,6");
+        assert_eq!(source.to_string(), "<inline>");
+        assert!(matches!(read_input("no-such-file"), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_read_parsed() {
+        let source = InputSource::Inline("1\nx\n".to_string());
+        let err = read_parsed(source, |text| parse_lines(text, |ln| parse_integer::<u32>(ln, ln))).unwrap_err();
+        assert_eq!(err.to_string(), "invalid input: <inline>:2:1: expected a digit, found \"x\"");
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let err = parse_lines("1\n2\nx3", |ln| {
+            ln.parse::<u32>().map_err(|_| ParseError::at_start(ln, "a number"))
+        }).unwrap_err().in_file("input");
+        assert_eq!(err.to_string(), "input:3:1: expected a number, found \"x3\"");
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer::<i32>("acc -99", &"acc -99"[4..]), Ok(-99));
+
+        let err = parse_integer::<u32>("12 ", "12 ").unwrap_err();
+        assert_eq!((err.column, err.found), (3, Some(" ".to_string())));
+
+        let err = parse_integer::<u8>("mem[256]", &"mem[256]"[4..7]).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "a smaller number"));
+
+        let err = parse_integer::<i32>("jmp ", &"jmp "[4..]).unwrap_err();
+        assert_eq!((err.column, err.found), (5, None));
+    }
+}
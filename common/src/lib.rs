@@ -0,0 +1,31 @@
+mod arith;
+mod cancel;
+pub mod combinator;
+mod certificate;
+mod counters;
+mod error;
+mod grid;
+mod input;
+mod logging;
+mod memory;
+mod progress;
+mod simulation;
+mod solution;
+mod text;
+
+pub use arith::{checked_add, checked_mul, checked_product};
+pub use cancel::{CancelToken, Iterations};
+pub use certificate::{certificate_field, certificate_indices, check_answer, Certificate, Certified};
+pub use counters::{count, count_round, take_counters, Counter, Counters};
+pub use error::{Error, ParseError};
+pub use grid::{Grid, Tile, DIRECTIONS};
+pub use input::{parse_integer, parse_lines, read_input, read_lines, read_parsed, InputSource};
+pub use logging::init_logging;
+pub use memory::{format_bytes, memory_usage, track_memory, MemoryUsage, TrackingAllocator};
+pub use progress::{report_progress, with_progress, Progress, ProgressReporter};
+pub use simulation::Simulation;
+pub use solution::{Details, Params, Solution, Unsolved};
+pub use text::{group_records, parse_comma_list, parse_comma_numbers, parse_numbers, split_sections, to_lines, Section};
+
+pub use num::{BigInt, BigUint};
+pub use serde_json::json;
@@ -0,0 +1,11 @@
+use env_logger::{Builder, Env};
+
+/// Sends solver logging to stderr. Only warnings and progress lines are shown unless
+/// `filter`, or failing that `RUST_LOG`, asks for more, e.g. `aoc16=debug` or `aoc8=trace,aoc9=debug`.
+pub fn init_logging(filter: Option<&str>) {
+    let mut builder = Builder::from_env(Env::default().default_filter_or("warn,progress=info"));
+    if let Some(filter) = filter {
+        builder.parse_filters(filter);
+    }
+    builder.format_timestamp(None).init();
+}
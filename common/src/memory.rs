@@ -0,0 +1,148 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Heap use on one thread since `track_memory` was last called there.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MemoryUsage {
+    /// The most heap held at once, beyond what was held when tracking started.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+/// The system allocator, counting each thread's allocations and the most heap it
+/// held at once. Install it with `#[global_allocator]` to make `memory_usage` work.
+pub struct TrackingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Usage {
+    live: i64,
+    peak: i64,
+    base: i64,
+    allocations: u64,
+}
+
+thread_local! {
+    static USAGE: Cell<Usage> = const { Cell::new(Usage{ live: 0, peak: 0, base: 0, allocations: 0 }) };
+}
+
+// Memory freed on another thread than it was allocated on counts against the
+// freeing thread, so `live` can go below where tracking started.
+fn record(change: i64, allocations: u64) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    // A thread being torn down has nothing left worth recording.
+    let _ = USAGE.try_with(|usage| {
+        let mut current = usage.get();
+        current.live = current.live + change;
+        current.peak = current.peak.max(current.live);
+        current.allocations = current.allocations + allocations;
+        usage.set(current);
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as i64, 1);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as i64, 1);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as i64), 0);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size as i64 - layout.size() as i64, 1);
+        }
+        new_ptr
+    }
+}
+
+/// Starts measuring this thread's heap use over again.
+pub fn track_memory() {
+    let _ = USAGE.try_with(|usage| {
+        let current = usage.get();
+        usage.set(Usage{ live: current.live, peak: current.live, base: current.live, allocations: 0 });
+    });
+}
+
+/// This thread's heap use since `track_memory`, if the `TrackingAllocator` is installed.
+pub fn memory_usage() -> Option<MemoryUsage> {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let current = USAGE.try_with(|usage| usage.get()).ok()?;
+    Some(MemoryUsage{ peak_bytes: (current.peak - current.base).max(0) as u64, allocations: current.allocations })
+}
+
+/// A byte count in the largest binary unit that keeps it at least 1, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < units.len() {
+        size = size / 1024.0;
+        unit = unit + 1;
+    }
+    if unit == 0 {
+        return format!("{} B", bytes);
+    }
+    format!("{:.1} {}", size, units[unit])
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} peak in {} allocations", format_bytes(self.peak_bytes), self.allocations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+    #[test]
+    fn test_memory_usage() {
+        let kept: Vec<u64> = vec![1, 2, 3];
+        track_memory();
+        let mut grown: Vec<u8> = Vec::with_capacity(1 << 20);
+        grown.push(1);
+        drop(grown);
+        let usage = memory_usage().unwrap();
+        assert!(usage.peak_bytes >= 1 << 20 && usage.peak_bytes < 2 << 20, "{:?}", usage);
+        assert_eq!(usage.allocations, 1);
+        drop(kept);
+
+        track_memory();
+        assert_eq!(memory_usage(), Some(MemoryUsage{ peak_bytes: 0, allocations: 0 }));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(MemoryUsage{ peak_bytes: 3 << 20, allocations: 7 }.to_string(), "3.0 MiB peak in 7 allocations");
+    }
+}
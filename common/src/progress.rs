@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// How far a long computation has got, e.g. 1200 of 30000000 turns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// What is being counted, in the plural, e.g. `turns` or `generations`.
+    pub unit: &'static str,
+    pub done: u64,
+    /// How many there will be, when that is known before they are all done.
+    pub total: Option<u64>,
+}
+
+/// Shows progress reported by solvers running under `with_progress`.
+pub trait ProgressReporter {
+    fn report(&self, progress: &Progress);
+}
+
+thread_local! {
+    static REPORTER: RefCell<Option<Rc<dyn ProgressReporter>>> = const { RefCell::new(None) };
+}
+
+/// Runs `solve`, passing the progress it reports on this thread to `reporter`.
+pub fn with_progress<T, F: FnOnce() -> T>(reporter: Rc<dyn ProgressReporter>, solve: F) -> T {
+    struct Restore(Option<Rc<dyn ProgressReporter>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            let _ = REPORTER.try_with(|current| current.replace(previous));
+        }
+    }
+
+    let _restore = Restore(REPORTER.with(|current| current.replace(Some(reporter))));
+    solve()
+}
+
+/// Passes `progress` on to this thread's reporter, if there is one.
+pub fn report_progress(progress: &Progress) {
+    // The reporter is cloned out first so it is free to report progress itself.
+    let reporter = REPORTER.with(|current| current.borrow().clone());
+    if let Some(reporter) = reporter {
+        reporter.report(progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Recorder(RefCell<Vec<Progress>>);
+
+    impl ProgressReporter for Recorder {
+        fn report(&self, progress: &Progress) {
+            self.0.borrow_mut().push(*progress);
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let first = Progress{ unit: "turns", done: 1, total: Some(3) };
+        report_progress(&first);
+
+        let recorder = Rc::new(Recorder(RefCell::new(Vec::new())));
+        with_progress(recorder.clone(), || report_progress(&first));
+        report_progress(&Progress{ unit: "turns", done: 2, total: Some(3) });
+        assert_eq!(*recorder.0.borrow(), vec![first]);
+    }
+}
@@ -0,0 +1,12 @@
+/// A puzzle whose state changes one step at a time, e.g. a round of seating or
+/// a navigation instruction, so it can be played back in a terminal.
+pub trait Simulation {
+    /// Moves on one step. Returns `false`, leaving the state as it was, once there are no steps left.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state, one line per row.
+    fn render(&self) -> String;
+
+    /// A one-line summary of the current state, e.g. the round and how many seats are taken.
+    fn status(&self) -> String;
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::{Certificate, Certified, Error};
+
+/// Named intermediate values behind an answer, e.g. a ship's final position.
+pub type Details = Map<String, Value>;
+
+/// Named values overriding a puzzle's constants, e.g. the preamble length of day 9,
+/// so the examples from the puzzle text can be solved as written.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Params {
+        Params(BTreeMap::new())
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|name| name.as_str())
+    }
+
+    /// The value of `name`, or `default` when it is not given.
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, Error> {
+        match self.0.get(name) {
+            Some(value) => value.parse().map_err(|_| Error::Param(format!("`{}` cannot be {:?}", name, value))),
+            None => Ok(default),
+        }
+    }
+}
+
+/// A day's puzzle: the input is parsed once into a typed model which both
+/// parts then work from.
+pub trait Solution {
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    /// Bumped whenever a change could give different answers or certificates
+    /// for the same input, so answers cached by the runner are solved again.
+    const VERSION: u32 = 1;
+
+    /// Names of the parameters `part1_with` and `part2_with` understand.
+    const PARAMS: &'static [&'static str] = &[];
+
+    fn parse(text: &str) -> Result<Self::Input, Error>;
+
+    /// Each part answers with the certificate that `check` confirms it by.
+    fn part1(input: &Self::Input) -> Result<Certified<Self::Answer1>, Error>;
+    fn part2(input: &Self::Input) -> Result<Certified<Self::Answer2>, Error>;
+
+    /// Part 1 with any of the puzzle's constants named in `PARAMS` overridden.
+    fn part1_with(input: &Self::Input, _params: &Params) -> Result<Certified<Self::Answer1>, Error> {
+        Self::part1(input)
+    }
+
+    fn part2_with(input: &Self::Input, _params: &Params) -> Result<Certified<Self::Answer2>, Error> {
+        Self::part2(input)
+    }
+
+    /// Intermediate values worth reporting alongside the answer to `part`.
+    /// They are worked out separately so they never slow down the part itself.
+    fn details(_input: &Self::Input, _part: u8) -> Result<Details, Error> {
+        Ok(Details::new())
+    }
+
+    /// The details behind an answer worked out with `params`.
+    fn details_with(input: &Self::Input, part: u8, _params: &Params) -> Result<Details, Error> {
+        Self::details(input, part)
+    }
+
+    /// Confirms that `certificate` proves `answer` to `part` for the input, without solving the part.
+    fn check(input: &Self::Input, part: u8, certificate: &Certificate, answer: &str) -> Result<(), Error>;
+
+    /// Checks an answer worked out with `params`.
+    fn check_with(input: &Self::Input, part: u8, certificate: &Certificate, answer: &str, _params: &Params) -> Result<(), Error> {
+        Self::check(input, part, certificate, answer)
+    }
+}
+
+/// Answer type for a part that has not been solved. It has no values, so the
+/// part can only ever return `Error::Unsolved`.
+#[derive(Debug)]
+pub enum Unsolved {}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::new();
+        params.set("preamble", "5");
+        params.set("color", "x");
+        assert_eq!(params.get_or("preamble", 25).unwrap(), 5);
+        assert_eq!(params.get_or("turns", 2020).unwrap(), 2020);
+        assert_eq!(params.get_or("color", 0).unwrap_err().to_string(), "invalid parameter: `color` cannot be \"x\"");
+        assert_eq!(params.names().collect::<Vec<&str>>(), vec!["color", "preamble"]);
+    }
+}
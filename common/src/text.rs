@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use crate::{parse_integer, parse_lines, ParseError};
+
+/// Every line of `text` as an owned string.
+pub fn to_lines(text: &str) -> Vec<String> {
+    text.lines().map(|ln| ln.to_string()).collect()
+}
+
+/// The whitespace separated integers on every line of `text`, e.g. one per line.
+pub fn parse_numbers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let lines = parse_lines(text, |ln| {
+        ln.split_whitespace().map(|token| parse_integer(ln, token)).collect::<Result<Vec<T>, ParseError>>()
+    })?;
+    Ok(lines.into_iter().flatten().collect())
+}
+
+/// Parses each comma separated item of `ln` with `parse_item`. Items are slices of `ln`,
+/// so errors can point into the line.
+pub fn parse_comma_list<T, F>(ln: &str, parse_item: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    ln.split(',').map(parse_item).collect()
+}
+
+/// The integers in a comma separated line like `0,3,6`.
+pub fn parse_comma_numbers<T: FromStr>(ln: &str) -> Result<Vec<T>, ParseError> {
+    parse_comma_list(ln, |item| parse_integer(ln, item))
+}
+
+/// A run of non-blank lines.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Section<'a> {
+    /// Line number of the section's first line, counting from 1.
+    pub line: usize,
+    /// The section's lines, without the trailing newline.
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    /// The section's lines with their line numbers in the whole text.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.text.lines().enumerate().map(move |(idx, ln)| (self.line + idx, ln))
+    }
+
+    /// Parses each line with `parse_line`, placing any error on the line it came from.
+    pub fn parse_lines<T, F>(&self, parse_line: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&str) -> Result<T, ParseError>,
+    {
+        parse_lines(self.text, parse_line).map_err(|err| err.on_line(self.line))
+    }
+}
+
+/// Splits `text` on blank lines. Any number of blank lines separate two sections.
+pub fn split_sections(text: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (idx, ln) in text.split_inclusive('\n').enumerate() {
+        let blank = ln.trim_end_matches(['\n', '\r']).is_empty();
+        match (start, blank) {
+            (None, false) => start = Some((idx + 1, offset)),
+            (Some((line, begin)), true) => {
+                sections.push(Section{ line: line, text: text[begin..offset].trim_end_matches(['\n', '\r']) });
+                start = None;
+            },
+            _ => {},
+        }
+        offset = offset + ln.len();
+    }
+    if let Some((line, begin)) = start {
+        sections.push(Section{ line: line, text: text[begin..].trim_end_matches(['\n', '\r']) });
+    }
+    sections
+}
+
+/// Groups the lines of `text` into records separated by blank lines, e.g. one group's answers.
+pub fn group_records(text: &str) -> Vec<Vec<&str>> {
+    split_sections(text).iter().map(|section| section.text.lines().collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_numbers::<u32>("1\n2 3\n\n4\n"), Ok(vec![1, 2, 3, 4]));
+        let err = parse_numbers::<u32>("1\n2x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn test_parse_comma_numbers() {
+        assert_eq!(parse_comma_numbers::<u32>("0,3,6"), Ok(vec![0, 3, 6]));
+        let err = parse_comma_numbers::<u32>("0,3,,6").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "a digit"));
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let text = "a\nb\n\n\nc\r\n\r\nd\n";
+        let sections = split_sections(text);
+        let found: Vec<(usize, &str)> = sections.iter().map(|s| (s.line, s.text)).collect();
+        assert_eq!(found, vec![(1, "a\nb"), (5, "c"), (7, "d")]);
+        assert_eq!(sections[0].lines().collect::<Vec<(usize, &str)>>(), vec![(1, "a"), (2, "b")]);
+
+        let err = sections[1].parse_lines(|ln| Err::<(), _>(ParseError::at_start(ln, "x"))).unwrap_err();
+        assert_eq!(err.line, 5);
+
+        assert_eq!(group_records("ab\nc\n\nd"), vec![vec!["ab", "c"], vec!["d"]]);
+        assert!(split_sections("\n\n").is_empty());
+    }
+}
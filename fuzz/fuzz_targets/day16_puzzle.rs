@@ -0,0 +1,7 @@
+#![no_main]
+use aoc_common::InputSource;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = aoc16::read_puzzle(InputSource::Inline(text.to_string()));
+});
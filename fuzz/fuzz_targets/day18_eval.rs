@@ -0,0 +1,6 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|ln: &str| {
+    let _ = aoc18::eval_ltr(&ln.to_string());
+});
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Whole rule sets, and the text of a single rule after its `N: `.
+fuzz_target!(|text: &str| {
+    let _ = aoc19::read_rules(text);
+    let _ = aoc19::parse_rule(&text.to_string());
+});
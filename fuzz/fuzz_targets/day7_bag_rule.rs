@@ -0,0 +1,6 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|ln: &str| {
+    let _ = aoc7::parse_single_bag_rule(ln.to_string());
+});